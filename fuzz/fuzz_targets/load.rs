@@ -0,0 +1,44 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mule_gb::{
+    HeaderRef, LoadOptions, load, load_at_offset, load_bank, load_header, load_with_options,
+    parse_streaming,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = load(data);
+    let _ = load_header(data);
+    let _ = load_with_options(
+        data,
+        LoadOptions {
+            strict: true,
+            verify_checksums: true,
+            allow_size_mismatch: true,
+        },
+    );
+
+    // the first byte picks the offset and bank, so both edges get hit
+    let selector = data.first().copied().unwrap_or(0) as usize;
+    let _ = load_at_offset(data, selector);
+    let _ = load_bank(data, selector);
+    let _ = parse_streaming(data, &mut |_| {});
+
+    if let Ok(header) = HeaderRef::new(data) {
+        let _ = header.entry_point();
+        let _ = header.logo_valid();
+        let _ = header.raw_title();
+        let _ = header.title();
+        let _ = header.manufacturer_code();
+        let _ = header.gbc_flag();
+        let _ = header.licensee_code();
+        let _ = header.sgb_flag();
+        let _ = header.cartridge_type();
+        let _ = header.rom_size();
+        let _ = header.ram_size();
+        let _ = header.destination_code();
+        let _ = header.rom_version();
+        let _ = header.checksum_valid();
+        let _ = header.global_checksum();
+    }
+});
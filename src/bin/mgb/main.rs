@@ -1,40 +1,349 @@
-use clap::{Parser, ValueEnum};
-use mule_gb::load;
-use serde_json;
-use serde_lexpr;
-use std::{fs::File, io::Read};
+mod yaml;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use mule_gb::hashes::{rom_hashes, to_hex};
+use mule_gb::scan::scan_dir;
+use mule_gb::{
+    CartridgeType, DATA_START, GBBinary, GLOBAL_CHECKSUM_OFFSET, HEADER_CHECKSUM_OFFSET, Header,
+    Interrupts, NINTENDO_LOGO, ROMSize, RestartCalls, fix_checksums, global_checksum_valid, load,
+    load_bank, load_header, verify_header_checksum,
+};
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use std::{
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::Path,
+};
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
-    file: String,
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// ROM file to read. Use - to read the ROM from stdin.
+    #[arg(required = true)]
+    file: Option<String>,
     /// Output format. Defaults to JSON. Possible options:
-    /// json|s-expr
-    #[arg(short, long)]
+    /// json|s-expr|yaml
+    #[arg(short, long, global = true)]
     format: Option<Format>,
+    /// Write the output to this file instead of stdout.
+    #[arg(short, long, global = true)]
+    output: Option<String>,
+    /// Write JSON on a single line instead of pretty printing it.
+    #[arg(long, global = true)]
+    compact: bool,
+    /// Encoding of the bank data. Defaults to array. Possible options:
+    /// array|base64
+    #[arg(long)]
+    bank_encoding: Option<BankEncoding>,
+    /// Only output the header, without vectors and bank data.
+    #[arg(long)]
+    header_only: bool,
+    /// Only output the given comma separated header fields, named as in the
+    /// serialised header, e.g. game_title,cartridge_type,rom_size.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "FIELDS",
+        conflicts_with_all = ["verify", "hash", "header_only"]
+    )]
+    fields: Option<Vec<String>>,
+    /// Only report whether the header checksum, global checksum and logo
+    /// are valid. Exits with a non-zero status if any of them is invalid.
+    #[arg(long)]
+    verify: bool,
+    /// Only print the digest of the complete ROM with the given algorithm.
+    #[arg(long, value_name = "ALGO")]
+    hash: Option<HashAlgo>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Parse the header of every .gb/.gbc file below DIR and print a summary
+    /// of each. Files that fail to parse are reported on stderr.
+    Scan { dir: String },
+    /// Write the raw 16 KiB bank BANK of FILE to the output.
+    ExtractBank { bank: usize, file: String },
+    /// Recompute the header and global checksum of FILE and write the
+    /// repaired ROM to the output. Works on the raw bytes, so the ROM does
+    /// not have to parse. Changed checksums are reported on stderr.
+    FixChecksums { file: String },
+}
+
+#[derive(Clone, ValueEnum)]
+enum HashAlgo {
+    Crc32,
+    Md5,
+    Sha1,
+}
+
+#[derive(Serialize)]
+struct VerifyReport {
+    header_checksum_ok: bool,
+    global_checksum_ok: bool,
+    logo_ok: bool,
+}
+
+impl VerifyReport {
+    fn new(data: &[u8]) -> VerifyReport {
+        VerifyReport {
+            header_checksum_ok: verify_header_checksum(data),
+            global_checksum_ok: global_checksum_valid(data),
+            logo_ok: data.get(0x104..0x134) == Some(&NINTENDO_LOGO[..]),
+        }
+    }
+
+    fn ok(&self) -> bool {
+        self.header_checksum_ok && self.global_checksum_ok && self.logo_ok
+    }
+}
+
+#[derive(Serialize)]
+struct ScanSummary<'a> {
+    path: String,
+    title: &'a str,
+    cartridge_type: &'a CartridgeType,
+    rom_size: &'a ROMSize,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum BankEncoding {
+    Array,
+    Base64,
+}
+
+/// `GBBinary` with the bank data written in the requested encoding.
+#[derive(Serialize)]
+#[serde(rename = "GBBinary")]
+struct BinaryOutput<'a> {
+    restart_calls: &'a RestartCalls,
+    interrupts: &'a Interrupts,
+    header: &'a Header,
+    bank_data: Banks<'a>,
+    declared_banks: usize,
+    inferred_banks: usize,
+}
+
+impl<'a> BinaryOutput<'a> {
+    fn new(gb_binary: &'a GBBinary, encoding: BankEncoding) -> BinaryOutput<'a> {
+        BinaryOutput {
+            restart_calls: &gb_binary.restart_calls,
+            interrupts: &gb_binary.interrupts,
+            header: &gb_binary.header,
+            bank_data: Banks {
+                banks: &gb_binary.bank_data,
+                encoding,
+            },
+            declared_banks: gb_binary.declared_banks,
+            inferred_banks: gb_binary.inferred_banks,
+        }
+    }
+}
+
+struct Banks<'a> {
+    banks: &'a [Vec<u8>],
+    encoding: BankEncoding,
+}
+
+impl Serialize for Banks<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.encoding {
+            BankEncoding::Array => self.banks.serialize(serializer),
+            BankEncoding::Base64 => mule_gb::base64::banks::serialize(self.banks, serializer),
+        }
+    }
+}
+
+/// The header fields selected with --fields, in the requested order.
+struct FieldSelection(Vec<(String, serde_json::Value)>);
+
+impl FieldSelection {
+    /// Selects `names` from the serialised header, so the valid names are
+    /// exactly the serialised field names and the same for every ROM.
+    fn new(header: &Header, names: &[String]) -> Result<FieldSelection, String> {
+        let serde_json::Value::Object(fields) =
+            serde_json::to_value(header).map_err(|e| e.to_string())?
+        else {
+            return Err(String::from("header does not serialise to a map"));
+        };
+        let mut selected = Vec::new();
+        for name in names {
+            match fields.get(name) {
+                Some(value) => selected.push((name.clone(), value.clone())),
+                None => {
+                    let valid: Vec<&str> = fields.keys().map(String::as_str).collect();
+                    return Err(format!(
+                        "unknown field {}, valid fields are: {}",
+                        name,
+                        valid.join(", ")
+                    ));
+                }
+            }
+        }
+        Ok(FieldSelection(selected))
+    }
+}
+
+impl Serialize for FieldSelection {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
 }
 
 #[derive(Clone, ValueEnum)]
 enum Format {
-    JSON,
+    Json,
     SExpr,
+    Yaml,
 }
 
 pub fn main() -> Result<(), String> {
     let args = Cli::parse();
 
-    let mut file = File::open(args.file).map_err(|e| e.to_string())?;
-    let mut buf = Vec::new();
-    file.read_to_end(&mut buf).map_err(|e| e.to_string())?;
-
-    let gb_binary = load(&buf)?;
-
-    let serialised = match args.format {
-        Some(Format::JSON) | None => {
-            serde_json::to_string_pretty(&gb_binary).expect("json serialisation")
+    let (output, verify_failed) = match &args.command {
+        Some(Command::Scan { dir }) => (scan(dir, &args)?.into_bytes(), false),
+        Some(Command::ExtractBank { bank, file }) => (extract_bank(*bank, file)?, false),
+        Some(Command::FixChecksums { file }) => (repair_checksums(file)?, false),
+        None => {
+            let file = args
+                .file
+                .as_deref()
+                .expect("file is required without a subcommand");
+            let (serialised, verify_failed) = inspect(file, &args)?;
+            (serialised.into_bytes(), verify_failed)
         }
-        Some(Format::SExpr) => serde_lexpr::to_string(&gb_binary).expect("lexpr serialisation"),
     };
-    print!("{}", serialised);
+
+    write_output(&args.output, &output)?;
+
+    if verify_failed {
+        std::process::exit(1);
+    }
 
     Ok(())
 }
+
+fn inspect(file: &str, args: &Cli) -> Result<(String, bool), String> {
+    let data = read_input(file)?;
+
+    let mut verify_failed = false;
+    let serialised = if let Some(algo) = &args.hash {
+        let hashes = rom_hashes(&data);
+        let digest = match algo {
+            HashAlgo::Crc32 => format!("{:08x}", hashes.crc32),
+            HashAlgo::Md5 => to_hex(&hashes.md5),
+            HashAlgo::Sha1 => to_hex(&hashes.sha1),
+        };
+        format!("{}\n", digest)
+    } else if args.verify {
+        let report = VerifyReport::new(&data);
+        verify_failed = !report.ok();
+        serialise(&report, args)?
+    } else if let Some(names) = &args.fields {
+        let header = load_header(&data).map_err(|e| e.to_string())?;
+        serialise(&FieldSelection::new(&header, names)?, args)?
+    } else if args.header_only {
+        let header = load_header(&data).map_err(|e| e.to_string())?;
+        serialise(&header, args)?
+    } else {
+        let gb_binary = load(&data).map_err(|e| e.to_string())?;
+        let encoding = args.bank_encoding.unwrap_or(BankEncoding::Array);
+        serialise(&BinaryOutput::new(&gb_binary, encoding), args)?
+    };
+    Ok((serialised, verify_failed))
+}
+
+fn scan(dir: &str, args: &Cli) -> Result<String, String> {
+    let entries = scan_dir(Path::new(dir)).map_err(|e| e.to_string())?;
+
+    let mut summaries = Vec::new();
+    for entry in &entries {
+        match &entry.header {
+            Ok(header) => summaries.push(ScanSummary {
+                path: entry.path.display().to_string(),
+                title: &header.game_title,
+                cartridge_type: &header.cartridge_type,
+                rom_size: &header.rom_size,
+            }),
+            Err(e) => eprintln!("{}: {}", entry.path.display(), e),
+        }
+    }
+    serialise(&summaries, args)
+}
+
+fn extract_bank(bank: usize, file: &str) -> Result<Vec<u8>, String> {
+    let data = read_input(file)?;
+    load_bank(&data, bank).map_err(|e| e.to_string())
+}
+
+fn repair_checksums(file: &str) -> Result<Vec<u8>, String> {
+    let mut data = read_input(file)?;
+    if data.len() < DATA_START {
+        return Err(format!(
+            "illegal ROM, too short for a header: {} bytes",
+            data.len()
+        ));
+    }
+    let header_before = data[HEADER_CHECKSUM_OFFSET];
+    let global_before = global_checksum(&data);
+    fix_checksums(&mut data);
+
+    if header_before != data[HEADER_CHECKSUM_OFFSET] {
+        eprintln!(
+            "header checksum: 0x{:02X} -> 0x{:02X}",
+            header_before, data[HEADER_CHECKSUM_OFFSET]
+        );
+    }
+    if global_before != global_checksum(&data) {
+        eprintln!(
+            "global checksum: 0x{:04X} -> 0x{:04X}",
+            global_before,
+            global_checksum(&data)
+        );
+    }
+    Ok(data)
+}
+
+fn global_checksum(data: &[u8]) -> u16 {
+    u16::from_be_bytes([
+        data[GLOBAL_CHECKSUM_OFFSET],
+        data[GLOBAL_CHECKSUM_OFFSET + 1],
+    ])
+}
+
+fn write_output(output: &Option<String>, data: &[u8]) -> Result<(), String> {
+    match output {
+        Some(path) => fs::write(path, data).map_err(|e| e.to_string()),
+        None => io::stdout().write_all(data).map_err(|e| e.to_string()),
+    }
+}
+
+fn read_input(file: &str) -> Result<Vec<u8>, String> {
+    let mut buf = Vec::new();
+    if file == "-" {
+        io::stdin()
+            .read_to_end(&mut buf)
+            .map_err(|e| e.to_string())?;
+    } else {
+        let mut file = File::open(file).map_err(|e| e.to_string())?;
+        file.read_to_end(&mut buf).map_err(|e| e.to_string())?;
+    }
+    Ok(buf)
+}
+
+fn serialise<T: Serialize>(value: &T, args: &Cli) -> Result<String, String> {
+    match args.format {
+        Some(Format::Json) | None if args.compact => {
+            serde_json::to_string(value).map_err(|e| e.to_string())
+        }
+        Some(Format::Json) | None => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+        Some(Format::SExpr) => serde_lexpr::to_string(value).map_err(|e| e.to_string()),
+        Some(Format::Yaml) => yaml::to_string(value).map_err(|e| e.to_string()),
+    }
+}
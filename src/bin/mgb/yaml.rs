@@ -0,0 +1,728 @@
+// A small YAML writer instead of serde_yaml, which is not available to this
+// crate. Output only, so it only needs to produce valid YAML, not parse it:
+// every string scalar is written double-quoted with JSON escapes, which YAML
+// accepts as is, and keys are written plain only if they consist of
+// alphanumerics and `_`. `:`, `#`, quotes and line breaks therefore never
+// reach the output unescaped.
+//
+// Values are collected with a serializer of their own instead of going
+// through `serde_json::Value`, whose maps are sorted, so fields keep their
+// declaration order as in the JSON and s-expression output.
+use serde::Serialize;
+use serde::ser::{self, Impossible};
+use std::fmt;
+
+/// Serialises a value as YAML. Sequences of scalars are written in flow
+/// style to keep the bank data compact.
+pub fn to_string<T: Serialize>(value: &T) -> Result<String, Error> {
+    let node = value.serialize(NodeSerializer)?;
+    let mut out = String::new();
+    match &node {
+        Node::Map(map) if !map.is_empty() => write_mapping(&mut out, map, 0),
+        Node::Seq(seq) if !node.is_flow() => write_sequence(&mut out, seq, 0),
+        _ => {
+            out.push_str(&node.flow());
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+#[derive(Debug)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Error {
+        Error(msg.to_string())
+    }
+}
+
+// A serialised value with its maps in serialisation order.
+enum Node {
+    Null,
+    Scalar(String),
+    String(String),
+    Seq(Vec<Node>),
+    Map(Vec<(String, Node)>),
+}
+
+impl Node {
+    // scalars, empty collections and sequences of scalars are written inline
+    fn is_flow(&self) -> bool {
+        match self {
+            Node::Map(map) => map.is_empty(),
+            Node::Seq(seq) => seq
+                .iter()
+                .all(|n| !matches!(n, Node::Map(_) | Node::Seq(_))),
+            _ => true,
+        }
+    }
+
+    fn flow(&self) -> String {
+        match self {
+            Node::Null => "null".to_string(),
+            Node::Scalar(s) => s.clone(),
+            Node::String(s) => quoted(s),
+            Node::Seq(seq) => {
+                let items: Vec<String> = seq.iter().map(Node::flow).collect();
+                format!("[{}]", items.join(", "))
+            }
+            Node::Map(_) => "{}".to_string(),
+        }
+    }
+}
+
+fn write_mapping(out: &mut String, map: &[(String, Node)], indent: usize) {
+    for (key, value) in map {
+        pad(out, indent);
+        out.push_str(&scalar_key(key));
+        out.push(':');
+        write_nested(out, value, indent);
+    }
+}
+
+fn write_sequence(out: &mut String, seq: &[Node], indent: usize) {
+    for value in seq {
+        pad(out, indent);
+        out.push('-');
+        write_nested(out, value, indent);
+    }
+}
+
+fn write_nested(out: &mut String, value: &Node, indent: usize) {
+    match value {
+        Node::Map(map) if !map.is_empty() => {
+            out.push('\n');
+            write_mapping(out, map, indent + 2);
+        }
+        Node::Seq(seq) if !value.is_flow() => {
+            out.push('\n');
+            write_sequence(out, seq, indent + 2);
+        }
+        _ => {
+            out.push(' ');
+            out.push_str(&value.flow());
+            out.push('\n');
+        }
+    }
+}
+
+// a JSON string is a valid YAML double-quoted scalar
+fn quoted(s: &str) -> String {
+    serde_json::Value::String(s.to_string()).to_string()
+}
+
+fn scalar_key(key: &str) -> String {
+    if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        key.to_string()
+    } else {
+        quoted(key)
+    }
+}
+
+fn pad(out: &mut String, indent: usize) {
+    for _ in 0..indent {
+        out.push(' ');
+    }
+}
+
+// Builds a `Node` with the same shape serde_json gives a value: enums are
+// externally tagged, bytes are sequences and map keys are strings.
+struct NodeSerializer;
+
+fn scalar<T: fmt::Display>(value: T) -> Result<Node, Error> {
+    Ok(Node::Scalar(value.to_string()))
+}
+
+fn float(value: f64) -> Result<Node, Error> {
+    if value.is_finite() {
+        scalar(value)
+    } else {
+        Ok(Node::Null)
+    }
+}
+
+fn tagged(variant: &str, node: Node) -> Node {
+    Node::Map(vec![(variant.to_string(), node)])
+}
+
+impl ser::Serializer for NodeSerializer {
+    type Ok = Node;
+    type Error = Error;
+    type SerializeSeq = SeqBuilder;
+    type SerializeTuple = SeqBuilder;
+    type SerializeTupleStruct = SeqBuilder;
+    type SerializeTupleVariant = SeqBuilder;
+    type SerializeMap = MapBuilder;
+    type SerializeStruct = MapBuilder;
+    type SerializeStructVariant = MapBuilder;
+
+    fn serialize_bool(self, v: bool) -> Result<Node, Error> {
+        scalar(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Node, Error> {
+        scalar(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Node, Error> {
+        scalar(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Node, Error> {
+        scalar(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Node, Error> {
+        scalar(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Node, Error> {
+        scalar(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Node, Error> {
+        scalar(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Node, Error> {
+        scalar(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Node, Error> {
+        scalar(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Node, Error> {
+        float(f64::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Node, Error> {
+        float(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Node, Error> {
+        Ok(Node::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Node, Error> {
+        Ok(Node::String(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Node, Error> {
+        Ok(Node::Seq(
+            v.iter().map(|b| Node::Scalar(b.to_string())).collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<Node, Error> {
+        Ok(Node::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Node, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Node, Error> {
+        Ok(Node::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Node, Error> {
+        Ok(Node::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Node, Error> {
+        Ok(Node::String(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Node, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Node, Error> {
+        Ok(tagged(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqBuilder, Error> {
+        Ok(SeqBuilder {
+            variant: None,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqBuilder, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SeqBuilder, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqBuilder, Error> {
+        Ok(SeqBuilder {
+            variant: Some(variant),
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<MapBuilder, Error> {
+        Ok(MapBuilder {
+            variant: None,
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<MapBuilder, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<MapBuilder, Error> {
+        Ok(MapBuilder {
+            variant: Some(variant),
+            entries: Vec::with_capacity(len),
+            key: None,
+        })
+    }
+}
+
+struct SeqBuilder {
+    variant: Option<&'static str>,
+    items: Vec<Node>,
+}
+
+impl SeqBuilder {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.items.push(value.serialize(NodeSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Node, Error> {
+        let seq = Node::Seq(self.items);
+        Ok(match self.variant {
+            Some(variant) => tagged(variant, seq),
+            None => seq,
+        })
+    }
+}
+
+impl ser::SerializeSeq for SeqBuilder {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqBuilder {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqBuilder {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqBuilder {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+struct MapBuilder {
+    variant: Option<&'static str>,
+    entries: Vec<(String, Node)>,
+    key: Option<String>,
+}
+
+impl MapBuilder {
+    fn entry<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> Result<(), Error> {
+        self.entries.push((key, value.serialize(NodeSerializer)?));
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Node, Error> {
+        let map = Node::Map(self.entries);
+        Ok(match self.variant {
+            Some(variant) => tagged(variant, map),
+            None => map,
+        })
+    }
+}
+
+impl ser::SerializeMap for MapBuilder {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error(String::from("map value without a key")))?;
+        self.entry(key, value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for MapBuilder {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.entry(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for MapBuilder {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.entry(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        self.finish()
+    }
+}
+
+// Map keys are written as strings, like serde_json does for string, char,
+// integer and unit variant keys.
+struct KeySerializer;
+
+fn key_error() -> Error {
+    Error(String::from("map keys must be strings or integers"))
+}
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_none(self) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_unit(self) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<String, Error> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(key_error())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_string;
+    use serde::Serialize;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct Escapes {
+        colon: &'static str,
+        comment: &'static str,
+        quotes: &'static str,
+        newline: &'static str,
+    }
+
+    #[derive(Serialize)]
+    enum Variant {
+        Unit,
+        Newtype(u8),
+        Struct { x: u8 },
+    }
+
+    #[test]
+    fn test_plain_keys_and_strings() {
+        let yaml = to_string(&json!({"game_title": "TETRIS", "banks": [1, 2]})).unwrap();
+        assert_eq!(yaml, "banks: [1, 2]\ngame_title: \"TETRIS\"\n");
+    }
+
+    #[test]
+    fn test_escaped_strings_keep_field_order() {
+        let yaml = to_string(&Escapes {
+            colon: "a: b",
+            comment: "a #b",
+            quotes: "say \"hi\" 'there'",
+            newline: "a\nb",
+        })
+        .unwrap();
+        assert_eq!(
+            yaml,
+            concat!(
+                "colon: \"a: b\"\n",
+                "comment: \"a #b\"\n",
+                "quotes: \"say \\\"hi\\\" 'there'\"\n",
+                "newline: \"a\\nb\"\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_escaped_keys_keep_insertion_order() {
+        let keys = ["e\nf", "a: b", "#c", "\"d\"", ""];
+        let map: Vec<(&str, usize)> = keys.iter().copied().zip(1..).collect();
+        let yaml = to_string(&SerializeAsMap(map)).unwrap();
+        assert_eq!(
+            yaml,
+            concat!(
+                "\"e\\nf\": 1\n",
+                "\"a: b\": 2\n",
+                "\"#c\": 3\n",
+                "\"\\\"d\\\"\": 4\n",
+                "\"\": 5\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_enum_variants() {
+        let yaml =
+            to_string(&[Variant::Unit, Variant::Newtype(1), Variant::Struct { x: 2 }]).unwrap();
+        assert_eq!(
+            yaml,
+            "- \"Unit\"\n-\n  Newtype: 1\n-\n  Struct:\n    x: 2\n"
+        );
+    }
+
+    #[test]
+    fn test_nested() {
+        let yaml = to_string(&json!({"header": {"title": "A:B"}, "list": [{"x": 1}]})).unwrap();
+        assert_eq!(yaml, "header:\n  title: \"A:B\"\nlist:\n  -\n    x: 1\n");
+    }
+
+    // a map that serialises its entries in the given order
+    struct SerializeAsMap(Vec<(&'static str, usize)>);
+
+    impl Serialize for SerializeAsMap {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+        }
+    }
+}
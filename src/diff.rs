@@ -0,0 +1,72 @@
+use crate::{GBBinary, Header};
+use alloc::vec::Vec;
+
+/// The differences between two ROMs, see `GBBinary::diff`.
+#[derive(Debug, Default, Clone)]
+pub struct RomDiff {
+    /// Names of the header fields that differ, in header order.
+    pub header_fields: Vec<&'static str>,
+    /// Indices of the banks that differ. A bank that only exists in one of
+    /// the ROMs counts as different.
+    pub banks: Vec<usize>,
+}
+
+impl RomDiff {
+    pub fn is_empty(&self) -> bool {
+        self.header_fields.is_empty() && self.banks.is_empty()
+    }
+}
+
+impl GBBinary {
+    /// Compares the header fields and the bank data of two ROMs. Banks are
+    /// compared byte for byte. Fields derived from other data, like
+    /// `checksum_valid`, are not compared.
+    pub fn diff(&self, other: &GBBinary) -> RomDiff {
+        let num_banks = self.bank_data.len().max(other.bank_data.len());
+        let banks = (0..num_banks)
+            .filter(|&i| self.bank_data.get(i) != other.bank_data.get(i))
+            .collect();
+
+        RomDiff {
+            header_fields: header_diff(&self.header, &other.header),
+            banks,
+        }
+    }
+}
+
+fn header_diff(a: &Header, b: &Header) -> Vec<&'static str> {
+    let fields = [
+        ("entry_point", a.entry_point == b.entry_point),
+        ("logo_data", a.logo_data == b.logo_data),
+        ("game_title", a.game_title == b.game_title),
+        ("raw_title", a.raw_title == b.raw_title),
+        (
+            "manufacturer_code",
+            a.manufacturer_code == b.manufacturer_code,
+        ),
+        ("gbc_flag", a.gbc_flag == b.gbc_flag),
+        ("gbc_flag_raw", a.gbc_flag_raw == b.gbc_flag_raw),
+        ("licensee_code", a.licensee_code == b.licensee_code),
+        (
+            "raw_old_licensee_code",
+            a.raw_old_licensee_code == b.raw_old_licensee_code,
+        ),
+        (
+            "raw_new_licensee_code",
+            a.raw_new_licensee_code == b.raw_new_licensee_code,
+        ),
+        ("sgb_flag", a.sgb_flag == b.sgb_flag),
+        ("cartridge_type", a.cartridge_type == b.cartridge_type),
+        ("rom_size", a.rom_size == b.rom_size),
+        ("ram_size", a.ram_size == b.ram_size),
+        ("destination_code", a.destination_code == b.destination_code),
+        ("rom_version", a.rom_version == b.rom_version),
+        ("checksum", a.checksum == b.checksum),
+        ("global_checksum", a.global_checksum == b.global_checksum),
+    ];
+    fields
+        .into_iter()
+        .filter(|(_, same)| !same)
+        .map(|(name, _)| name)
+        .collect()
+}
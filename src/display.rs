@@ -0,0 +1,357 @@
+use crate::{
+    CartridgeType, DestinationCode, GBCFlag, Header, LicenseeCode, RAMSize, ROMSize, SGBFlag,
+};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+impl fmt::Display for LicenseeCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LicenseeCode::None => "None",
+            LicenseeCode::Unknown { old: Some(old), .. } => {
+                return write!(f, "Unknown (0x{:02X})", old);
+            }
+            LicenseeCode::Unknown { new: Some(new), .. } => {
+                return write!(f, "Unknown (\"{}\")", String::from_utf8_lossy(new));
+            }
+            LicenseeCode::Unknown { .. } => "Unknown",
+            LicenseeCode::Nintendo => "Nintendo",
+            LicenseeCode::Capcom => "Capcom",
+            LicenseeCode::ElectronicArts => "Electronic Arts",
+            LicenseeCode::HudsonSoft => "Hudson Soft",
+            LicenseeCode::BAi => "B-AI",
+            LicenseeCode::Kss => "KSS",
+            LicenseeCode::PlanningOfficeWada => "Planning Office WADA",
+            LicenseeCode::PCMComplete => "PCM Complete",
+            LicenseeCode::SanX => "San-X",
+            LicenseeCode::Kemco => "Kemco",
+            LicenseeCode::Seta => "SETA Corporation",
+            LicenseeCode::Viacom => "Viacom",
+            LicenseeCode::Bandai => "Bandai",
+            LicenseeCode::OceanAcclaim => "Ocean Software/Acclaim Entertainment",
+            LicenseeCode::Konami => "Konami",
+            LicenseeCode::HectorSoft => "HectorSoft",
+            LicenseeCode::Taito => "Taito",
+            LicenseeCode::Banpresto => "Banpresto",
+            LicenseeCode::UbiSoft => "Ubi Soft",
+            LicenseeCode::Atlus => "Atlus",
+            LicenseeCode::MalibuInteractive => "Malibu Interactive",
+            LicenseeCode::Angel => "Angel",
+            LicenseeCode::BulletProofSoftware => "Bullet-Proof Software",
+            LicenseeCode::Irem => "Irem",
+            LicenseeCode::Absolute => "Absolute",
+            LicenseeCode::Acclaim => "Acclaim Entertainment",
+            LicenseeCode::Activision => "Activision",
+            LicenseeCode::SammyUSA => "Sammy USA Corporation",
+            LicenseeCode::HiTechExpressions => "Hi Tech Expressions",
+            LicenseeCode::Ljn => "LJN",
+            LicenseeCode::Matchbox => "Matchbox",
+            LicenseeCode::Mattel => "Mattel",
+            LicenseeCode::MiltonBradley => "Milton Bradley Company",
+            LicenseeCode::Titus => "Titus Interactive",
+            LicenseeCode::Virgin => "Virgin Games Ltd.",
+            LicenseeCode::LucasfilmGames => "Lucasfilm Games",
+            LicenseeCode::Ocean => "Ocean Software",
+            LicenseeCode::Infogrames => "Infogrames",
+            LicenseeCode::Interplay => "Interplay Entertainment",
+            LicenseeCode::Broderbund => "Broderbund",
+            LicenseeCode::SculpturedSoftware => "Sculptured Software",
+            LicenseeCode::TheSalesCurve => "The Sales Curve Limited",
+            LicenseeCode::Thq => "THQ",
+            LicenseeCode::Accolade => "Accolade",
+            LicenseeCode::MisawaEntertainment => "Misawa Entertainment",
+            LicenseeCode::Lozc => "LOZC",
+            LicenseeCode::TokumaShoten => "Tokuma Shoten",
+            LicenseeCode::TsukudaOriginal => "Tsukuda Original",
+            LicenseeCode::Chunsoft => "Chunsoft Co.",
+            LicenseeCode::VideoSystem => "Video System",
+            LicenseeCode::Varie => "Varie",
+            LicenseeCode::YonezawaSPal => "Yonezawa/S'Pal",
+            LicenseeCode::Kaneko => "Kaneko",
+            LicenseeCode::PackInVideo => "Pack-In-Video",
+            LicenseeCode::BottomUp => "Bottom Up",
+            LicenseeCode::KonamiYuGiOh => "Konami (Yu-Gi-Oh!)",
+            LicenseeCode::Mto => "MTO",
+            LicenseeCode::Kodansha => "Kodansha",
+            LicenseeCode::Namco => "Namco",
+            LicenseeCode::HotB => "HOT-B",
+            LicenseeCode::Jaleco => "Jaleco",
+            LicenseeCode::CoconutsJapan => "Coconuts Japan",
+            LicenseeCode::EliteSystems => "Elite Systems",
+            LicenseeCode::ITCEntertainment => "ITC Entertainment",
+            LicenseeCode::Yanoman => "Yanoman",
+            LicenseeCode::JapanClary => "Japan Clary",
+            LicenseeCode::EntertainmentInteractive => "Entertainment Interactive",
+            LicenseeCode::Gremlin => "Gremlin",
+            LicenseeCode::SpectrumHoloByte => "Spectrum HoloByte",
+            LicenseeCode::USGold => "U.S. Gold",
+            LicenseeCode::GameTek => "GameTek",
+            LicenseeCode::ParkPlace => "Park Place",
+            LicenseeCode::Mindscape => "Mindscape",
+            LicenseeCode::Romstar => "Romstar",
+            LicenseeCode::NaxatSoft => "Naxat Soft",
+            LicenseeCode::Tradewest => "Tradewest",
+            LicenseeCode::ElectroBrain => "Electro Brain",
+            LicenseeCode::TriffixEntertainment => "Triffix Entertainment",
+            LicenseeCode::MicroProse => "MicroProse",
+            LicenseeCode::VicTokai => "Vic Tokai Corp.",
+            LicenseeCode::Ape => "Ape Inc.",
+            LicenseeCode::IMax => "I'Max",
+            LicenseeCode::TsubarayaProductions => "Tsubaraya Productions",
+            LicenseeCode::Arc => "Arc",
+            LicenseeCode::NihonBussan => "Nihon Bussan",
+            LicenseeCode::Tecmo => "Tecmo",
+            LicenseeCode::Imagineer => "Imagineer",
+            LicenseeCode::Nova => "Nova",
+            LicenseeCode::HoriElectric => "Hori Electric",
+            LicenseeCode::Kawada => "Kawada",
+            LicenseeCode::Takara => "Takara",
+            LicenseeCode::TechnosJapan => "Technos Japan",
+            LicenseeCode::ToeiAnimation => "Toei Animation",
+            LicenseeCode::Toho => "Toho",
+            LicenseeCode::ASCIIOrNexsoft => "ASCII Corporation or Nexsoft",
+            LicenseeCode::SquareEnix => "Square Enix",
+            LicenseeCode::HALLaboratory => "HAL Laboratory",
+            LicenseeCode::Snk => "SNK",
+            LicenseeCode::PonyCanyon => "Pony Canyon",
+            LicenseeCode::CultureBrain => "Culture Brain",
+            LicenseeCode::Sunsoft => "Sunsoft",
+            LicenseeCode::SonyImagesoft => "Sony Imagesoft",
+            LicenseeCode::SammyCorporation => "Sammy Corporation",
+            LicenseeCode::Square => "Square",
+            LicenseeCode::DataEast => "Data East",
+            LicenseeCode::TonkinHouse => "Tonkin House",
+            LicenseeCode::Koei => "Koei",
+            LicenseeCode::Ufl => "UFL",
+            LicenseeCode::UltraGames => "Ultra Games",
+            LicenseeCode::Vap => "VAP, Inc.",
+            LicenseeCode::UseCorporation => "Use Corporation",
+            LicenseeCode::Meldac => "Meldac",
+            LicenseeCode::Sofel => "SOFEL",
+            LicenseeCode::Quest => "Quest",
+            LicenseeCode::SigmaEnterprises => "Sigma Enterprises",
+            LicenseeCode::ASKKodansha => "ASK Kodansha Co.",
+            LicenseeCode::CopyaSystem => "Copya System",
+            LicenseeCode::Tomy => "Tomy",
+            LicenseeCode::NipponComputerSystems => "Nippon Computer Systems",
+            LicenseeCode::HumanEntertainment => "Human Ent.",
+            LicenseeCode::Altron => "Altron",
+            LicenseeCode::TowaChiki => "Towa Chiki",
+            LicenseeCode::Yutaka => "Yutaka",
+            LicenseeCode::Epoch => "Epoch",
+            LicenseeCode::Athena => "Athena",
+            LicenseeCode::AsmikAce => "Asmik Ace Entertainment",
+            LicenseeCode::Natsume => "Natsume",
+            LicenseeCode::KingRecords => "King Records",
+            LicenseeCode::EpicSonyRecords => "Epic/Sony Records",
+            LicenseeCode::Igs => "IGS",
+            LicenseeCode::AWave => "A Wave",
+            LicenseeCode::ExtremeEntertainment => "Extreme Entertainment",
+        };
+        f.write_str(name)
+    }
+}
+
+impl fmt::Display for CartridgeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CartridgeType::ROMOnly => "ROM only",
+            CartridgeType::MBC1 => "MBC1",
+            CartridgeType::MBC1xRAM => "MBC1+RAM",
+            CartridgeType::MBC1xRAMxBattery => "MBC1+RAM+Battery",
+            CartridgeType::MBC2 => "MBC2",
+            CartridgeType::MBC2xBattery => "MBC2+Battery",
+            CartridgeType::ROMxRAM => "ROM+RAM",
+            CartridgeType::ROMxRAMxBattery => "ROM+RAM+Battery",
+            CartridgeType::MMM01 => "MMM01",
+            CartridgeType::MMM01xRAM => "MMM01+RAM",
+            CartridgeType::MMM01xRAMxBattery => "MMM01+RAM+Battery",
+            CartridgeType::MBC3xTimerxBattery => "MBC3+Timer+Battery",
+            CartridgeType::MBC3xTimerxRAMxBattery => "MBC3+Timer+RAM+Battery",
+            CartridgeType::MBC3 => "MBC3",
+            CartridgeType::MBC3xRAM => "MBC3+RAM",
+            CartridgeType::MBC3xRAMxBattery => "MBC3+RAM+Battery",
+            CartridgeType::MBC5 => "MBC5",
+            CartridgeType::MBC5xRAM => "MBC5+RAM",
+            CartridgeType::MBC5xRAMxBattery => "MBC5+RAM+Battery",
+            CartridgeType::MBC5xRumble => "MBC5+Rumble",
+            CartridgeType::MBC5xRumblexRAM => "MBC5+Rumble+RAM",
+            CartridgeType::MBC5xRumblexRAMxBattery => "MBC5+Rumble+RAM+Battery",
+            CartridgeType::MBC6 => "MBC6",
+            CartridgeType::MBC7xSensorxRumblexRAMxBattery => "MBC7+Sensor+Rumble+RAM+Battery",
+            CartridgeType::PocketCamera => "Pocket Camera",
+            CartridgeType::BandaiTama5 => "Bandai TAMA5",
+            CartridgeType::HuC3 => "HuC3",
+            CartridgeType::HuC1xRAMxBattery => "HuC1+RAM+Battery",
+            CartridgeType::Unknown(v) => return write!(f, "Unknown (0x{:02X})", v),
+        };
+        f.write_str(name)
+    }
+}
+
+// formats a byte count as KiB or MiB, whichever is exact
+fn write_size(f: &mut fmt::Formatter<'_>, bytes: usize) -> fmt::Result {
+    const MIB: usize = 1024 * 1024;
+    if bytes >= MIB && bytes.is_multiple_of(MIB) {
+        write!(f, "{} MiB", bytes / MIB)
+    } else {
+        write!(f, "{} KiB", bytes / 1024)
+    }
+}
+
+impl fmt::Display for ROMSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let ROMSize::Unknown(v) = self {
+            return write!(f, "Unknown (0x{:02X})", v);
+        }
+        write_size(f, self.bytes())?;
+        write!(f, " ({} banks)", self.num_banks())
+    }
+}
+
+impl fmt::Display for RAMSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RAMSize::None => f.write_str("None"),
+            RAMSize::Unknown(v) => write!(f, "Unknown (0x{:02X})", v),
+            _ => write_size(f, self.bytes()),
+        }
+    }
+}
+
+impl fmt::Display for GBCFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            GBCFlag::GBOnly => "GB only",
+            GBCFlag::GBCAndGB => "GBC and GB",
+            GBCFlag::GBCOnly => "GBC only",
+        })
+    }
+}
+
+impl fmt::Display for SGBFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SGBFlag::NoSGB => f.write_str("No SGB support"),
+            SGBFlag::SGBSupport => f.write_str("SGB support"),
+            SGBFlag::Unknown(v) => write!(f, "Unknown (0x{:02X})", v),
+        }
+    }
+}
+
+impl fmt::Display for DestinationCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DestinationCode::Japanese => f.write_str("Japanese"),
+            DestinationCode::NonJapanese => f.write_str("Non-Japanese"),
+            DestinationCode::Unknown(v) => write!(f, "Unknown (0x{:02X})", v),
+        }
+    }
+}
+
+fn validity(valid: bool) -> &'static str {
+    if valid { "valid" } else { "invalid" }
+}
+
+impl fmt::Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.fields().iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{:<18} {}", format!("{}:", name), value)?;
+        }
+        Ok(())
+    }
+}
+
+// a byte count formatted with write_size
+struct Size(usize);
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_size(f, self.0)
+    }
+}
+
+impl Header {
+    /// Every header field as display name and formatted value, in the order
+    /// used by the `Display` impl: the title and the fields describing the
+    /// cartridge first, then the entry point, logo and checksums. The
+    /// manufacturer code is left out for pre-CGB cartridges that have none.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        let [e0, e1, e2, e3] = self.entry_point;
+        let mut fields = vec![("Title", self.game_title.clone())];
+        if !self.manufacturer_code.is_empty() {
+            fields.push(("Manufacturer code", self.manufacturer_code.clone()));
+        }
+        fields.extend([
+            ("Licensee", self.licensee_code.to_string()),
+            ("Cartridge type", self.cartridge_type.to_string()),
+            ("ROM size", self.rom_size.to_string()),
+            ("RAM size", self.ram_size.to_string()),
+            ("GBC", self.gbc_flag.to_string()),
+            ("SGB", self.sgb_flag.to_string()),
+            ("Destination", self.destination_code.to_string()),
+            ("ROM version", self.rom_version.to_string()),
+            (
+                "Entry point",
+                format!("{:02X} {:02X} {:02X} {:02X}", e0, e1, e2, e3),
+            ),
+            ("Logo", String::from(validity(self.logo_valid))),
+            (
+                "Header checksum",
+                format!(
+                    "0x{:02X} ({})",
+                    self.checksum,
+                    validity(self.checksum_valid)
+                ),
+            ),
+            (
+                "Global checksum",
+                format!(
+                    "0x{:04X} ({})",
+                    self.global_checksum,
+                    validity(self.global_checksum_valid)
+                ),
+            ),
+        ]);
+        fields
+    }
+
+    /// A single line description for logs and list views, e.g.
+    /// `SUPER MARIOLAND (Nintendo) — MBC1, 64 KiB ROM, no RAM, GB, Japanese, v0`.
+    pub fn summary(&self) -> String {
+        let rom = match self.rom_size {
+            ROMSize::Unknown(_) => format!("{} ROM", self.rom_size),
+            _ => format!("{} ROM", Size(self.rom_size.bytes())),
+        };
+        let ram = match self.ram_size {
+            RAMSize::None => String::from("no RAM"),
+            RAMSize::Unknown(_) => format!("{} RAM", self.ram_size),
+            _ => format!("{} RAM", Size(self.ram_size.bytes())),
+        };
+        let mut hardware = String::from(match self.gbc_flag {
+            GBCFlag::GBOnly => "GB",
+            GBCFlag::GBCAndGB => "CGB+GB",
+            GBCFlag::GBCOnly => "CGB",
+        });
+        if self.sgb_flag == SGBFlag::SGBSupport {
+            hardware.push_str("+SGB");
+        }
+        format!(
+            "{} ({}) — {}, {}, {}, {}, {}, v{}",
+            self.display_title(),
+            self.licensee_code,
+            self.cartridge_type,
+            rom,
+            ram,
+            hardware,
+            self.destination_code,
+            self.rom_version
+        )
+    }
+}
@@ -0,0 +1,157 @@
+use core::fmt;
+
+#[derive(Debug)]
+pub enum GBError {
+    /// Reading the ROM from its source failed.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    /// The CGB flag at `offset` has a value the spec does not define.
+    /// Only returned in strict mode, see `LoadOptions::strict`.
+    UnsupportedGbcFlag { value: u8, offset: usize },
+    /// The SGB flag at `offset` is neither 0x00 nor 0x03.
+    /// Only returned in strict mode, see `LoadOptions::strict`.
+    UnsupportedSgbFlag { value: u8, offset: usize },
+    /// The cartridge type at `offset` has a value the spec does not define.
+    /// Only returned in strict mode, see `LoadOptions::strict`.
+    UnsupportedCartridgeType { value: u8, offset: usize },
+    /// The ROM size at `offset` has a value the spec does not define.
+    /// Returned in strict mode, but also in lenient mode unless
+    /// `LoadOptions::allow_size_mismatch` is set, as an unknown size
+    /// declares no length to check the data against. `GBBinary::validate`
+    /// returns it for the same reason.
+    UnsupportedRomSize { value: u8, offset: usize },
+    /// The RAM size at `offset` has a value the spec does not define.
+    /// Only returned in strict mode, see `LoadOptions::strict`.
+    UnsupportedRamSize { value: u8, offset: usize },
+    /// The destination code at `offset` has a value the spec does not define.
+    /// Only returned in strict mode, see `LoadOptions::strict`.
+    UnsupportedDestinationCode { value: u8, offset: usize },
+    /// The input ended before `needed` more bytes could be read at `offset`.
+    UnexpectedEof { offset: usize, needed: usize },
+    /// The input is too short to contain a ROM header.
+    TooShort { len: usize },
+    /// The ROM length does not match the declared ROM size.
+    SizeMismatch { declared: usize, actual: usize },
+    /// The header checksum at 0x14D does not match, see `LoadOptions::verify_checksums`.
+    HeaderChecksumMismatch { stored: u8, computed: u8 },
+    /// The global checksum at 0x14E does not match, see `LoadOptions::verify_checksums`.
+    GlobalChecksumMismatch { stored: u16, computed: u16 },
+    /// A `GBBinary` holds a different number of banks than its ROM size
+    /// declares, see `GBBinary::validate`.
+    BankCountMismatch { expected: usize, actual: usize },
+    /// A bank of a `GBBinary` is not `BANK_BYTES` long.
+    BankLengthMismatch { bank: usize, len: usize },
+    /// The bank `index` does not exist in a ROM with `banks` banks.
+    BankOutOfRange { index: usize, banks: usize },
+    /// The ROM contains less bank data than its declared ROM size requires.
+    NotEnoughBankData { expected: usize, actual: usize },
+}
+
+impl fmt::Display for GBError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            GBError::Io(e) => write!(f, "io error: {}", e),
+            GBError::UnsupportedGbcFlag { value, offset } => {
+                write!(
+                    f,
+                    "unsupported GBC flag: {:x} at offset {:x}",
+                    value, offset
+                )
+            }
+            GBError::UnsupportedSgbFlag { value, offset } => {
+                write!(
+                    f,
+                    "unsupported SGB flag: {:x} at offset {:x}",
+                    value, offset
+                )
+            }
+            GBError::UnsupportedCartridgeType { value, offset } => write!(
+                f,
+                "unsupported cartridge type: {:x} at offset {:x}",
+                value, offset
+            ),
+            GBError::UnsupportedRomSize { value, offset } => {
+                write!(
+                    f,
+                    "unsupported rom size: {:x} at offset {:x}",
+                    value, offset
+                )
+            }
+            GBError::UnsupportedRamSize { value, offset } => {
+                write!(
+                    f,
+                    "unsupported ram size: {:x} at offset {:x}",
+                    value, offset
+                )
+            }
+            GBError::UnsupportedDestinationCode { value, offset } => write!(
+                f,
+                "unsupported destination code: {:x} at offset {:x}",
+                value, offset
+            ),
+            GBError::UnexpectedEof { offset, needed } => write!(
+                f,
+                "unexpected end of data: needed {} bytes at offset {:x}",
+                needed, offset
+            ),
+            GBError::TooShort { len } => {
+                write!(f, "illegal ROM, too short for a header: {} bytes", len)
+            }
+            GBError::SizeMismatch { declared, actual } => write!(
+                f,
+                "ROM size mismatch: header declares {} bytes, ROM has {} bytes",
+                declared, actual
+            ),
+            GBError::HeaderChecksumMismatch { stored, computed } => write!(
+                f,
+                "header checksum mismatch: stored {:x}, computed {:x}",
+                stored, computed
+            ),
+            GBError::GlobalChecksumMismatch { stored, computed } => write!(
+                f,
+                "global checksum mismatch: stored {:x}, computed {:x}",
+                stored, computed
+            ),
+            GBError::BankCountMismatch { expected, actual } => write!(
+                f,
+                "bank count mismatch: ROM size declares {} banks, got {}",
+                expected, actual
+            ),
+            GBError::BankLengthMismatch { bank, len } => {
+                write!(
+                    f,
+                    "bank {} has {} bytes instead of {}",
+                    bank,
+                    len,
+                    crate::BANK_BYTES
+                )
+            }
+            GBError::BankOutOfRange { index, banks } => {
+                write!(f, "bank {} out of range, ROM has {} banks", index, banks)
+            }
+            GBError::NotEnoughBankData { expected, actual } => write!(
+                f,
+                "illegal ROM, not enough bank data. Expected {} bytes, got {} bytes in ROM",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl core::error::Error for GBError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            GBError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for GBError {
+    fn from(e: std::io::Error) -> Self {
+        GBError::Io(e)
+    }
+}
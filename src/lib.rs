@@ -1,17 +1,321 @@
-mod reader;
+//! Parser for Game Boy and Game Boy Color ROM images.
+//!
+//! The `std` feature is enabled by default. Without it the crate is
+//! `no_std` and only needs `alloc`: the parser, checksums, hashes and
+//! writer stay available, while serde support, `io` based loading, the
+//! directory scan and `Header::field_offsets` require `std`. The `base64`
+//! feature serialises bank data as base64 strings, see `base64::banks`.
+//! The `mmap` feature adds `load_mmap` on 64-bit Linux and macOS, on other
+//! targets it has no effect.
+//!
+//! With the opt-in `serde-kebab-case` feature enum variants serialise as
+//! stable kebab-case identifiers instead of their Rust names, e.g.
+//! `CartridgeType::MBC5xRumblexRAMxBattery` becomes
+//! `"mbc5-rumble-ram-battery"` and `GBCFlag::GBCAndGB` becomes
+//! `"gbc-and-gb"`. Field names stay snake_case in both formats.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "base64")]
+pub mod base64;
+mod diff;
+mod display;
+mod error;
+pub mod hashes;
+#[cfg(all(
+    feature = "mmap",
+    target_pointer_width = "64",
+    any(target_os = "linux", target_os = "macos")
+))]
+mod mmap;
+pub mod reader;
+#[cfg(feature = "std")]
+pub mod scan;
+#[cfg(feature = "sgb")]
+pub mod sgb;
+mod stream;
+mod view;
+mod warning;
+pub mod writer;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::{Range, RangeInclusive};
+pub use diff::RomDiff;
+pub use error::GBError;
+#[cfg(all(
+    feature = "mmap",
+    target_pointer_width = "64",
+    any(target_os = "linux", target_os = "macos")
+))]
+pub use mmap::load_mmap;
 use reader::DataReader;
-use serde::Serialize;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::io::Read;
+pub use stream::{ParseEvent, parse_streaming};
+pub use view::{HeaderRef, RomView};
+pub use warning::Warning;
+use writer::DataWriter;
 
-#[derive(Serialize)]
+#[derive(Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct GBBinary {
     pub restart_calls: RestartCalls,
     pub interrupts: Interrupts,
     pub header: Header,
+    /// Serialised as an array of byte arrays, or as one base64 string per
+    /// bank with the `base64` feature.
+    #[cfg_attr(feature = "base64", serde(with = "base64::banks"))]
     pub bank_data: Vec<Vec<u8>>,
+    /// The number of banks the header's ROM size declares.
+    #[cfg_attr(feature = "std", serde(default))]
+    pub declared_banks: usize,
+    /// The number of banks in `bank_data`. Only differs from `declared_banks`
+    /// if the ROM was loaded with `LoadOptions::allow_size_mismatch`, in that
+    /// case it is inferred from the length of the data.
+    #[cfg_attr(feature = "std", serde(default))]
+    pub inferred_banks: usize,
 }
 
-#[derive(Serialize)]
+impl GBBinary {
+    /// Serialises the binary back into ROM bytes. The vectors and header
+    /// fields are written over the concatenated bank data, the checksums
+    /// are written as stored and not recomputed. The raw title and licensee
+    /// bytes are written back unchanged unless the fields parsed from them
+    /// were modified, so an unmodified binary gives back the loaded ROM.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let data: Vec<u8> = self.bank_data.concat();
+        let mut writer = if data.len() < DATA_START {
+            let mut padded = data;
+            padded.resize(DATA_START, 0);
+            DataWriter::new_with_data(padded)
+        } else {
+            DataWriter::new_with_data(data)
+        };
+        write_restart_calls(&mut writer, &self.restart_calls);
+        write_interrupts(&mut writer, &self.interrupts);
+        write_header(&mut writer, &self.header);
+        writer.into_data()
+    }
+
+    /// Checks that the bank data matches the ROM size: one entry per bank,
+    /// each `BANK_BYTES` long. `declared_banks` must match the header and
+    /// `bank_data` must hold `inferred_banks` entries. Bank 0 includes the vectors and the
+    /// header, so it has the same length as every other bank. Useful for
+    /// instances that were not produced by `load`, e.g. deserialised ones.
+    pub fn validate(&self) -> Result<(), GBError> {
+        if let ROMSize::Unknown(value) = self.header.rom_size {
+            return Err(GBError::UnsupportedRomSize {
+                value,
+                offset: 0x148,
+            });
+        }
+        let declared = self.header.rom_size.num_banks();
+        if self.declared_banks != declared {
+            return Err(GBError::BankCountMismatch {
+                expected: declared,
+                actual: self.declared_banks,
+            });
+        }
+        if self.bank_data.len() != self.inferred_banks {
+            return Err(GBError::BankCountMismatch {
+                expected: self.inferred_banks,
+                actual: self.bank_data.len(),
+            });
+        }
+        match self.bank_data.iter().position(|b| b.len() != BANK_BYTES) {
+            Some(bank) => Err(GBError::BankLengthMismatch {
+                bank,
+                len: self.bank_data[bank].len(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// The length of `data`, the file this binary was loaded from, minus the
+    /// declared ROM size. Positive for overdumps, negative for trimmed dumps.
+    /// See `LoadOptions::allow_size_mismatch` for loading those.
+    pub fn file_size_delta(&self, data: &[u8]) -> i64 {
+        data.len() as i64 - self.header.rom_size.bytes() as i64
+    }
+
+    /// The bytes of `data`, the file this binary was loaded from, beyond
+    /// the declared ROM size, e.g. an appended patch or metadata. Empty if
+    /// the file is not larger than the declared ROM size. Note that with
+    /// `LoadOptions::allow_size_mismatch` these bytes are part of the
+    /// inferred banks as well.
+    pub fn trailer<'a>(&self, data: &'a [u8]) -> &'a [u8] {
+        data.get(self.header.rom_size.bytes()..).unwrap_or(&[])
+    }
+
+    /// Up to `len` bytes of `data` starting at the entry point 0x100, for
+    /// disassembling the startup code. Clipped to the end of `data`. See
+    /// `Header::entry_jump_target` for following the usual jump.
+    pub fn startup_code<'a>(&self, data: &'a [u8], len: usize) -> &'a [u8] {
+        let start = HEADER_RANGE.start.min(data.len());
+        let end = start.saturating_add(len).min(data.len());
+        &data[start..end]
+    }
+
+    pub fn bank_slices(&self) -> impl Iterator<Item = &[u8]> {
+        self.bank_data.iter().map(|b| b.as_slice())
+    }
+
+    /// The size of the ROM without the padding at its end, i.e. the offset
+    /// after the last byte that is not padding. The pad byte is detected
+    /// from the last byte of the ROM: 0xFF or 0x00, otherwise the ROM is
+    /// assumed to be unpadded and its full size is returned. See
+    /// `used_size_with` to specify the pad byte.
+    pub fn used_size(&self) -> usize {
+        match self.bank_data.last().and_then(|bank| bank.last()) {
+            Some(&pad @ (0x00 | 0xFF)) => self.used_size_with(pad),
+            _ => self.bank_data.iter().map(Vec::len).sum(),
+        }
+    }
+
+    /// Like `used_size`, but with a caller supplied pad byte.
+    pub fn used_size_with(&self, pad: u8) -> usize {
+        let mut size: usize = self.bank_data.iter().map(Vec::len).sum();
+        for bank in self.bank_data.iter().rev() {
+            match bank.iter().rposition(|b| *b != pad) {
+                Some(last) => return size - bank.len() + last + 1,
+                None => size -= bank.len(),
+            }
+        }
+        0
+    }
+
+    /// The CRC32 of every bank, in bank order. Computed on each call, so
+    /// `load` does not pay for it.
+    pub fn bank_crcs(&self) -> Vec<u32> {
+        self.bank_slices().map(hashes::crc32).collect()
+    }
+
+    /// The CRC32 of the complete ROM image, as listed in No-Intro DAT files.
+    pub fn crc32(&self) -> u32 {
+        hashes::crc32_chunks(self.bank_slices())
+    }
+
+    /// A short identity string built from the global checksum, cartridge
+    /// type, ROM size and cleaned title, e.g. `1841-00-00-TETRIS`. Nothing
+    /// has to be hashed, which makes it cheap for grouping large scans, but
+    /// unlike `crc32` or the digests in `hashes` two different ROMs can
+    /// easily share a fingerprint.
+    pub fn fingerprint(&self) -> String {
+        format!(
+            "{:04X}-{:02X}-{:02X}-{}",
+            self.header.global_checksum,
+            self.header.cartridge_type.code(),
+            self.header.rom_size.code(),
+            self.header.display_title()
+        )
+    }
+
+    /// Looks up the ROM by its CRC32 with a caller supplied database, e.g.
+    /// one built from a No-Intro DAT file.
+    pub fn identify_with<F: Fn(u32) -> Option<String>>(&self, lookup: F) -> Option<String> {
+        lookup(self.crc32())
+    }
+
+    /// The number of 8 KiB external RAM banks, see `RAMSize::num_banks`.
+    /// The banks persist across power cycles if `cartridge_type.has_battery()`.
+    ///
+    /// MBC2 cartridges return 0, their 512 half bytes of built-in RAM are not
+    /// banked and declared as no RAM in the header.
+    pub fn ram_bank_count(&self) -> usize {
+        self.header.ram_size.num_banks()
+    }
+
+    /// The size of the battery backed RAM, i.e. of the save file. Zero if the
+    /// cartridge has no battery.
+    ///
+    /// MBC2 has 512 half bytes of built-in RAM, only the lower 4 bits of each
+    /// address are stored. Save files keep one address per byte, so 512 bytes
+    /// are returned although the header declares no RAM.
+    pub fn battery_ram_bytes(&self) -> usize {
+        let cartridge_type = &self.header.cartridge_type;
+        if !cartridge_type.has_battery() {
+            0
+        } else if cartridge_type.mbc_kind() == MbcKind::Mbc2 {
+            MBC2_RAM_BYTES
+        } else {
+            self.header.ram_size.bytes()
+        }
+    }
+
+    /// Whether the cartridge has an MBC3 real time clock, i.e. save files
+    /// need the RTC state next to the RAM. See `CartridgeType::has_rtc` for
+    /// the register layout.
+    pub fn rtc_present(&self) -> bool {
+        self.header.cartridge_type.has_rtc()
+    }
+
+    /// Whether this looks like an MBC1 multi-cart (MBC1M), which wires the
+    /// banking bits differently so each game sees its own 256 KiB.
+    ///
+    /// The header does not mark these carts, so this is a heuristic: the ROM
+    /// must be an MBC1 ROM of exactly 1 MiB (64 banks) and at least one of
+    /// the 256 KiB aligned banks 0x10, 0x20 or 0x30 must contain the Nintendo
+    /// logo at 0x104, i.e. start with a header of its own. A regular game
+    /// that happens to embed the logo at one of those offsets is reported as
+    /// a multi-cart too.
+    pub fn is_mbc1_multicart(&self) -> bool {
+        if self.header.cartridge_type.mbc_kind() != MbcKind::Mbc1 || self.bank_data.len() != 64 {
+            return false;
+        }
+        let logo = 0x104..0x104 + NINTENDO_LOGO.len();
+        // `bank_data` is public and may hold short banks, missing data is
+        // no logo
+        [0x10, 0x20, 0x30].iter().any(|&bank| {
+            self.bank_data
+                .get(bank)
+                .and_then(|b| b.get(logo.clone()))
+                .is_some_and(|b| b == NINTENDO_LOGO)
+        })
+    }
+
+    /// All memory the cartridge provides, see `MemoryLayout`.
+    pub fn memory_layout(&self) -> MemoryLayout {
+        let cartridge_type = &self.header.cartridge_type;
+        let mbc2 = cartridge_type.mbc_kind() == MbcKind::Mbc2;
+        MemoryLayout {
+            rom_bytes: self.bank_data.len() * BANK_BYTES,
+            external_ram_bytes: if mbc2 {
+                0
+            } else {
+                self.header.ram_size.bytes()
+            },
+            internal_ram_bytes: if mbc2 { MBC2_RAM_BYTES } else { 0 },
+            has_rtc: cartridge_type.has_rtc(),
+        }
+    }
+}
+
+/// The memory an emulator has to provide for a cartridge, see
+/// `GBBinary::memory_layout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct MemoryLayout {
+    /// The size of the loaded bank data.
+    pub rom_bytes: usize,
+    /// The external RAM declared at 0x149.
+    pub external_ram_bytes: usize,
+    /// RAM built into the mapper, i.e. the 512 half bytes of MBC2 with one
+    /// byte per address.
+    pub internal_ram_bytes: usize,
+    /// Whether the cartridge has real time clock registers.
+    pub has_rtc: bool,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct RestartCalls {
     pub rst_0: [u8; 8],
     pub rst_1: [u8; 8],
@@ -23,7 +327,8 @@ pub struct RestartCalls {
     pub rst_7: [u8; 8],
 }
 
-#[derive(Serialize)]
+#[derive(Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Interrupts {
     pub v_blank: [u8; 8],
     pub lcd_stat: [u8; 8],
@@ -32,65 +337,549 @@ pub struct Interrupts {
     pub joypad: [u8; 8],
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-kebab-case", serde(rename_all = "kebab-case"))]
 pub enum LicenseeCode {
     None,
-    Unknown,
+    /// A licensee code that is not in the mapping. Holds the raw value of
+    /// whichever code (old or new) was used to determine the licensee.
+    Unknown {
+        old: Option<u8>,
+        new: Option<[u8; 2]>,
+    },
     Nintendo,
     Capcom,
+    ElectronicArts,
+    HudsonSoft,
+    BAi,
+    Kss,
+    PlanningOfficeWada,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "pcm-complete"))]
+    PCMComplete,
+    SanX,
+    Kemco,
+    Seta,
+    Viacom,
     Bandai,
+    OceanAcclaim,
+    Konami,
+    HectorSoft,
+    Taito,
+    Banpresto,
+    UbiSoft,
+    Atlus,
+    MalibuInteractive,
+    Angel,
+    BulletProofSoftware,
+    Irem,
+    Absolute,
+    Acclaim,
+    Activision,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "sammy-usa"))]
+    SammyUSA,
+    HiTechExpressions,
+    Ljn,
+    Matchbox,
+    Mattel,
+    MiltonBradley,
+    Titus,
+    Virgin,
+    LucasfilmGames,
+    Ocean,
+    Infogrames,
+    Interplay,
+    Broderbund,
+    SculpturedSoftware,
+    TheSalesCurve,
+    Thq,
+    Accolade,
+    MisawaEntertainment,
+    Lozc,
+    TokumaShoten,
+    TsukudaOriginal,
+    Chunsoft,
+    VideoSystem,
+    Varie,
+    YonezawaSPal,
+    Kaneko,
+    PackInVideo,
+    BottomUp,
+    KonamiYuGiOh,
+    Mto,
+    Kodansha,
     Namco,
+    HotB,
+    Jaleco,
+    CoconutsJapan,
+    EliteSystems,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "itc-entertainment"))]
+    ITCEntertainment,
+    Yanoman,
+    JapanClary,
+    EntertainmentInteractive,
+    Gremlin,
+    SpectrumHoloByte,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "us-gold"))]
+    USGold,
+    GameTek,
+    ParkPlace,
+    Mindscape,
+    Romstar,
+    NaxatSoft,
+    Tradewest,
+    ElectroBrain,
+    TriffixEntertainment,
+    MicroProse,
+    VicTokai,
+    Ape,
+    IMax,
+    TsubarayaProductions,
+    Arc,
+    NihonBussan,
+    Tecmo,
+    Imagineer,
+    Nova,
+    HoriElectric,
+    Kawada,
+    Takara,
+    TechnosJapan,
+    ToeiAnimation,
+    Toho,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "ascii-or-nexsoft"))]
+    ASCIIOrNexsoft,
+    SquareEnix,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "hal-laboratory"))]
+    HALLaboratory,
+    Snk,
+    PonyCanyon,
+    CultureBrain,
+    Sunsoft,
+    SonyImagesoft,
+    SammyCorporation,
+    Square,
+    DataEast,
+    TonkinHouse,
+    Koei,
+    Ufl,
+    UltraGames,
+    Vap,
+    UseCorporation,
+    Meldac,
+    Sofel,
+    Quest,
+    SigmaEnterprises,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "ask-kodansha"))]
+    ASKKodansha,
+    CopyaSystem,
+    Tomy,
+    NipponComputerSystems,
+    HumanEntertainment,
+    Altron,
+    TowaChiki,
+    Yutaka,
+    Epoch,
+    Athena,
+    AsmikAce,
+    Natsume,
+    KingRecords,
+    EpicSonyRecords,
+    Igs,
+    AWave,
+    ExtremeEntertainment,
 }
 
-#[derive(Serialize, Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-kebab-case", serde(rename_all = "kebab-case"))]
 pub enum GBCFlag {
     /// Not explictely set, only support the GameBoy Classic
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "gb-only"))]
     GBOnly,
     /// support the GameBoy Color and the GameBoy Classic
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "gbc-and-gb"))]
     GBCAndGB,
     /// only supports the GameBoy Color
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "gbc-only"))]
     GBCOnly,
 }
 
-#[derive(Serialize, Clone, Copy)]
+impl GBCFlag {
+    /// Whether the ROM runs on the original Game Boy (DMG). Bit 7 of the
+    /// flag marks CGB support, bit 6 additionally drops DMG support.
+    pub fn supports_dmg(&self) -> bool {
+        matches!(self, GBCFlag::GBOnly | GBCFlag::GBCAndGB)
+    }
+
+    /// Whether the ROM refuses to run on anything but a CGB.
+    pub fn requires_cgb(&self) -> bool {
+        *self == GBCFlag::GBCOnly
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-kebab-case", serde(rename_all = "kebab-case"))]
 pub enum SGBFlag {
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "no-sgb"))]
     NoSGB,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "sgb-support"))]
     SGBSupport,
+    /// Any value other than 0x00 and 0x03. The boot ROM only enables SGB
+    /// functions for exactly 0x03, so this behaves like `NoSGB`.
+    Unknown(u8),
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-kebab-case", serde(rename_all = "kebab-case"))]
 pub enum CartridgeType {
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "rom-only"))]
     ROMOnly,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc1"))]
     MBC1,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc1-ram"))]
     MBC1xRAM,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc1-ram-battery"))]
     MBC1xRAMxBattery,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc2"))]
     MBC2,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc2-battery"))]
     MBC2xBattery,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "rom-ram"))]
     ROMxRAM,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "rom-ram-battery"))]
     ROMxRAMxBattery,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mmm01"))]
     MMM01,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mmm01-ram"))]
     MMM01xRAM,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mmm01-ram-battery"))]
     MMM01xRAMxBattery,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc3-timer-battery"))]
     MBC3xTimerxBattery,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc3-timer-ram-battery"))]
     MBC3xTimerxRAMxBattery,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc3"))]
     MBC3,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc3-ram"))]
     MBC3xRAM,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc3-ram-battery"))]
     MBC3xRAMxBattery,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc5"))]
     MBC5,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc5-ram"))]
     MBC5xRAM,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc5-ram-battery"))]
     MBC5xRAMxBattery,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc5-rumble"))]
     MBC5xRumble,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc5-rumble-ram"))]
     MBC5xRumblexRAM,
+    #[cfg_attr(
+        feature = "serde-kebab-case",
+        serde(rename = "mbc5-rumble-ram-battery")
+    )]
     MBC5xRumblexRAMxBattery,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "mbc6"))]
     MBC6,
+    #[cfg_attr(
+        feature = "serde-kebab-case",
+        serde(rename = "mbc7-sensor-rumble-ram-battery")
+    )]
     MBC7xSensorxRumblexRAMxBattery,
     PocketCamera,
     BandaiTama5,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "huc3"))]
     HuC3,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "huc1-ram-battery"))]
     HuC1xRAMxBattery,
+    /// A cartridge type byte not in the spec, only produced in lenient mode.
+    Unknown(u8),
+}
+
+/// The memory bank controller family of a cartridge, independent of the
+/// peripherals attached to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-kebab-case", serde(rename_all = "kebab-case"))]
+pub enum MbcKind {
+    /// No memory bank controller (plain ROM, optionally with RAM).
+    None,
+    Mbc1,
+    Mbc2,
+    Mbc3,
+    Mbc5,
+    Mbc6,
+    Mbc7,
+    Mmm01,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "huc1"))]
+    HuC1,
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "huc3"))]
+    HuC3,
+    PocketCamera,
+    BandaiTama5,
+    /// The controller of an unknown cartridge type.
+    Unknown,
+}
+
+impl CartridgeType {
+    /// The byte at 0x147 that encodes this cartridge type.
+    pub fn code(&self) -> u8 {
+        match self {
+            CartridgeType::ROMOnly => 0x00,
+            CartridgeType::MBC1 => 0x01,
+            CartridgeType::MBC1xRAM => 0x02,
+            CartridgeType::MBC1xRAMxBattery => 0x03,
+            CartridgeType::MBC2 => 0x05,
+            CartridgeType::MBC2xBattery => 0x06,
+            CartridgeType::ROMxRAM => 0x08,
+            CartridgeType::ROMxRAMxBattery => 0x09,
+            CartridgeType::MMM01 => 0x0B,
+            CartridgeType::MMM01xRAM => 0x0C,
+            CartridgeType::MMM01xRAMxBattery => 0x0D,
+            CartridgeType::MBC3xTimerxBattery => 0x0F,
+            CartridgeType::MBC3xTimerxRAMxBattery => 0x10,
+            CartridgeType::MBC3 => 0x11,
+            CartridgeType::MBC3xRAM => 0x12,
+            CartridgeType::MBC3xRAMxBattery => 0x13,
+            CartridgeType::MBC5 => 0x19,
+            CartridgeType::MBC5xRAM => 0x1A,
+            CartridgeType::MBC5xRAMxBattery => 0x1B,
+            CartridgeType::MBC5xRumble => 0x1C,
+            CartridgeType::MBC5xRumblexRAM => 0x1D,
+            CartridgeType::MBC5xRumblexRAMxBattery => 0x1E,
+            CartridgeType::MBC6 => 0x20,
+            CartridgeType::MBC7xSensorxRumblexRAMxBattery => 0x22,
+            CartridgeType::PocketCamera => 0xFC,
+            CartridgeType::BandaiTama5 => 0xFD,
+            CartridgeType::HuC3 => 0xFE,
+            CartridgeType::HuC1xRAMxBattery => 0xFF,
+            CartridgeType::Unknown(v) => *v,
+        }
+    }
+
+    pub fn mbc_kind(&self) -> MbcKind {
+        match self {
+            CartridgeType::ROMOnly | CartridgeType::ROMxRAM | CartridgeType::ROMxRAMxBattery => {
+                MbcKind::None
+            }
+            CartridgeType::MBC1 | CartridgeType::MBC1xRAM | CartridgeType::MBC1xRAMxBattery => {
+                MbcKind::Mbc1
+            }
+            CartridgeType::MBC2 | CartridgeType::MBC2xBattery => MbcKind::Mbc2,
+            CartridgeType::MMM01 | CartridgeType::MMM01xRAM | CartridgeType::MMM01xRAMxBattery => {
+                MbcKind::Mmm01
+            }
+            CartridgeType::MBC3xTimerxBattery
+            | CartridgeType::MBC3xTimerxRAMxBattery
+            | CartridgeType::MBC3
+            | CartridgeType::MBC3xRAM
+            | CartridgeType::MBC3xRAMxBattery => MbcKind::Mbc3,
+            CartridgeType::MBC5
+            | CartridgeType::MBC5xRAM
+            | CartridgeType::MBC5xRAMxBattery
+            | CartridgeType::MBC5xRumble
+            | CartridgeType::MBC5xRumblexRAM
+            | CartridgeType::MBC5xRumblexRAMxBattery => MbcKind::Mbc5,
+            CartridgeType::MBC6 => MbcKind::Mbc6,
+            CartridgeType::MBC7xSensorxRumblexRAMxBattery => MbcKind::Mbc7,
+            CartridgeType::PocketCamera => MbcKind::PocketCamera,
+            CartridgeType::BandaiTama5 => MbcKind::BandaiTama5,
+            CartridgeType::HuC3 => MbcKind::HuC3,
+            CartridgeType::HuC1xRAMxBattery => MbcKind::HuC1,
+            CartridgeType::Unknown(_) => MbcKind::Unknown,
+        }
+    }
+
+    /// Whether the cartridge has external RAM. The 512x4 bits RAM built into
+    /// the MBC2 chip do not count as external RAM.
+    pub fn has_ram(&self) -> bool {
+        matches!(
+            self,
+            CartridgeType::MBC1xRAM
+                | CartridgeType::MBC1xRAMxBattery
+                | CartridgeType::ROMxRAM
+                | CartridgeType::ROMxRAMxBattery
+                | CartridgeType::MMM01xRAM
+                | CartridgeType::MMM01xRAMxBattery
+                | CartridgeType::MBC3xTimerxRAMxBattery
+                | CartridgeType::MBC3xRAM
+                | CartridgeType::MBC3xRAMxBattery
+                | CartridgeType::MBC5xRAM
+                | CartridgeType::MBC5xRAMxBattery
+                | CartridgeType::MBC5xRumblexRAM
+                | CartridgeType::MBC5xRumblexRAMxBattery
+                | CartridgeType::MBC7xSensorxRumblexRAMxBattery
+                | CartridgeType::PocketCamera
+                | CartridgeType::HuC3
+                | CartridgeType::HuC1xRAMxBattery
+        )
+    }
+
+    /// Whether the cartridge RAM (or the MBC2 internal RAM) is battery backed.
+    pub fn has_battery(&self) -> bool {
+        matches!(
+            self,
+            CartridgeType::MBC1xRAMxBattery
+                | CartridgeType::MBC2xBattery
+                | CartridgeType::ROMxRAMxBattery
+                | CartridgeType::MMM01xRAMxBattery
+                | CartridgeType::MBC3xTimerxBattery
+                | CartridgeType::MBC3xTimerxRAMxBattery
+                | CartridgeType::MBC3xRAMxBattery
+                | CartridgeType::MBC5xRAMxBattery
+                | CartridgeType::MBC5xRumblexRAMxBattery
+                | CartridgeType::MBC7xSensorxRumblexRAMxBattery
+                | CartridgeType::PocketCamera
+                | CartridgeType::HuC3
+                | CartridgeType::HuC1xRAMxBattery
+        )
+    }
+
+    /// Whether the cartridge contains a real time clock.
+    pub fn has_timer(&self) -> bool {
+        matches!(
+            self,
+            CartridgeType::MBC3xTimerxBattery
+                | CartridgeType::MBC3xTimerxRAMxBattery
+                | CartridgeType::HuC3
+        )
+    }
+
+    /// Whether the cartridge has the MBC3 real time clock, whose state
+    /// emulators persist next to the RAM. Unlike `has_timer` this excludes
+    /// the HuC3, its clock works differently.
+    ///
+    /// The clock is accessed by mapping an RTC register instead of a RAM
+    /// bank, via 0x08-0x0C written to 0x4000-0x5FFF:
+    /// - 0x08 seconds (0-59)
+    /// - 0x09 minutes (0-59)
+    /// - 0x0A hours (0-23)
+    /// - 0x0B lower 8 bits of the day counter
+    /// - 0x0C bit 0: bit 8 of the day counter, bit 6: halt,
+    ///   bit 7: day counter carry
+    ///
+    /// Writing 0x00 then 0x01 to 0x6000-0x7FFF latches the current time
+    /// into the registers. Save files commonly append the 5 current and 5
+    /// latched registers as 4 byte little-endian values followed by a unix
+    /// timestamp, 48 bytes in total.
+    pub fn has_rtc(&self) -> bool {
+        matches!(
+            self,
+            CartridgeType::MBC3xTimerxBattery | CartridgeType::MBC3xTimerxRAMxBattery
+        )
+    }
+
+    pub fn has_rumble(&self) -> bool {
+        matches!(
+            self,
+            CartridgeType::MBC5xRumble
+                | CartridgeType::MBC5xRumblexRAM
+                | CartridgeType::MBC5xRumblexRAMxBattery
+                | CartridgeType::MBC7xSensorxRumblexRAMxBattery
+        )
+    }
+
+    /// Whether the cartridge contains the MBC7 accelerometer.
+    pub fn has_sensor(&self) -> bool {
+        matches!(self, CartridgeType::MBC7xSensorxRumblexRAMxBattery)
+    }
+
+    /// All `has_*` properties of the cartridge type as a single set.
+    pub fn features(&self) -> CartridgeFeatures {
+        [
+            (self.has_ram(), CartridgeFeatures::RAM),
+            (self.has_battery(), CartridgeFeatures::BATTERY),
+            (self.has_timer(), CartridgeFeatures::TIMER),
+            (self.has_rumble(), CartridgeFeatures::RUMBLE),
+            (self.has_sensor(), CartridgeFeatures::SENSOR),
+        ]
+        .into_iter()
+        .filter(|(has, _)| *has)
+        .fold(CartridgeFeatures::empty(), |all, (_, feature)| {
+            all | feature
+        })
+    }
+}
+
+/// A set of cartridge peripherals, see `CartridgeType::features`. Combine
+/// flags with `|` and test them with `contains`, `iter` yields the single
+/// flags that are set.
+///
+/// This is a small hand-written version of what the `bitflags` crate would
+/// generate, which is not a dependency of this crate.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CartridgeFeatures(u8);
+
+impl CartridgeFeatures {
+    pub const RAM: CartridgeFeatures = CartridgeFeatures(1 << 0);
+    pub const BATTERY: CartridgeFeatures = CartridgeFeatures(1 << 1);
+    pub const TIMER: CartridgeFeatures = CartridgeFeatures(1 << 2);
+    pub const RUMBLE: CartridgeFeatures = CartridgeFeatures(1 << 3);
+    pub const SENSOR: CartridgeFeatures = CartridgeFeatures(1 << 4);
+
+    /// Every single flag, in bit order.
+    pub const ALL: [CartridgeFeatures; 5] = [
+        CartridgeFeatures::RAM,
+        CartridgeFeatures::BATTERY,
+        CartridgeFeatures::TIMER,
+        CartridgeFeatures::RUMBLE,
+        CartridgeFeatures::SENSOR,
+    ];
+
+    pub const fn empty() -> CartridgeFeatures {
+        CartridgeFeatures(0)
+    }
+
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether all flags of `other` are set.
+    pub const fn contains(&self, other: CartridgeFeatures) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// The single flags that are set, in bit order.
+    pub fn iter(&self) -> impl Iterator<Item = CartridgeFeatures> + use<> {
+        let set = *self;
+        CartridgeFeatures::ALL
+            .into_iter()
+            .filter(move |flag| set.contains(*flag))
+    }
 }
 
-#[derive(Serialize, Copy, Clone)]
+impl core::ops::BitOr for CartridgeFeatures {
+    type Output = CartridgeFeatures;
+
+    fn bitor(self, rhs: CartridgeFeatures) -> CartridgeFeatures {
+        CartridgeFeatures(self.0 | rhs.0)
+    }
+}
+
+impl core::ops::BitOrAssign for CartridgeFeatures {
+    fn bitor_assign(&mut self, rhs: CartridgeFeatures) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl core::ops::BitAnd for CartridgeFeatures {
+    type Output = CartridgeFeatures;
+
+    fn bitand(self, rhs: CartridgeFeatures) -> CartridgeFeatures {
+        CartridgeFeatures(self.0 & rhs.0)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-kebab-case", serde(rename_all = "kebab-case"))]
 pub enum ROMSize {
     NoBanking,
     Banks4,
@@ -104,49 +893,180 @@ pub enum ROMSize {
     Banks128,
     Banks256,
     Banks512,
+    /// A ROM size byte not in the spec, only produced in lenient mode.
+    Unknown(u8),
 }
 
+impl ROMSize {
+    /// The byte at 0x148 that encodes this ROM size.
+    pub fn code(&self) -> u8 {
+        match *self {
+            ROMSize::NoBanking => 0x00,
+            ROMSize::Banks4 => 0x01,
+            ROMSize::Banks8 => 0x02,
+            ROMSize::Banks16 => 0x03,
+            ROMSize::Banks32 => 0x04,
+            ROMSize::Banks64 => 0x05,
+            ROMSize::Banks128 => 0x06,
+            ROMSize::Banks256 => 0x07,
+            ROMSize::Banks512 => 0x08,
+            ROMSize::Banks72 => 0x52,
+            ROMSize::Banks80 => 0x53,
+            ROMSize::Banks96 => 0x54,
+            ROMSize::Unknown(v) => v,
+        }
+    }
+
+    pub fn num_banks(&self) -> usize {
+        match self {
+            ROMSize::NoBanking => 2,
+            ROMSize::Banks4 => 4,
+            ROMSize::Banks8 => 8,
+            ROMSize::Banks16 => 16,
+            ROMSize::Banks32 => 32,
+            ROMSize::Banks64 => 64,
+            ROMSize::Banks72 => 72,
+            ROMSize::Banks80 => 80,
+            ROMSize::Banks96 => 96,
+            ROMSize::Banks128 => 128,
+            ROMSize::Banks256 => 256,
+            ROMSize::Banks512 => 512,
+            // the number of banks can not be derived from an unknown size
+            ROMSize::Unknown(_) => 0,
+        }
+    }
+
+    /// The ROM size in bytes, e.g. 32768 for `NoBanking`.
+    pub fn bytes(&self) -> usize {
+        self.num_banks() * BANK_BYTES
+    }
+}
+
+#[deprecated(note = "use ROMSize::num_banks instead")]
 pub fn num_banks(rom_size: ROMSize) -> usize {
-    match rom_size {
-        ROMSize::NoBanking => 2,
-        ROMSize::Banks4 => 4,
-        ROMSize::Banks8 => 8,
-        ROMSize::Banks16 => 16,
-        ROMSize::Banks32 => 32,
-        ROMSize::Banks64 => 64,
-        ROMSize::Banks72 => 72,
-        ROMSize::Banks80 => 80,
-        ROMSize::Banks96 => 96,
-        ROMSize::Banks128 => 128,
-        ROMSize::Banks256 => 256,
-        ROMSize::Banks512 => 512,
-    }
-}
-
-#[derive(Serialize, Copy, Clone)]
+    rom_size.num_banks()
+}
+
+/// External RAM size declared at 0x149. The codes are not ordered by size:
+/// the spec assigns 0x04 to 128 KiB and only later added 0x05 for 64 KiB.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-kebab-case", serde(rename_all = "kebab-case"))]
 pub enum RAMSize {
+    /// 0x00
     None,
+    /// 0x01, unused by licensed cartridges
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "kb2"))]
     KB2,
+    /// 0x02
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "kb8"))]
     KB8,
+    /// 0x03
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "kb32"))]
     KB32,
+    /// 0x05, out of order, see above
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "kb64"))]
     KB64,
+    /// 0x04
+    #[cfg_attr(feature = "serde-kebab-case", serde(rename = "kb128"))]
     KB128,
+    /// A RAM size byte not in the spec, only produced in lenient mode.
+    Unknown(u8),
 }
 
-#[derive(Serialize, Copy, Clone)]
+pub const RAM_BANK_BYTES: usize = 8 * 1024;
+/// The number of 4 bit cells in the MBC2 built-in RAM.
+pub const MBC2_RAM_BYTES: usize = 512;
+
+impl RAMSize {
+    /// The byte at 0x149 that encodes this RAM size.
+    pub fn code(&self) -> u8 {
+        match *self {
+            RAMSize::None => 0x00,
+            RAMSize::KB2 => 0x01,
+            RAMSize::KB8 => 0x02,
+            RAMSize::KB32 => 0x03,
+            RAMSize::KB128 => 0x04,
+            RAMSize::KB64 => 0x05,
+            RAMSize::Unknown(v) => v,
+        }
+    }
+
+    /// The size of the external cartridge RAM in bytes.
+    ///
+    /// MBC2 cartridges report `None` here although the MBC2 chip contains
+    /// 512x4 bits of internal RAM.
+    pub fn bytes(&self) -> usize {
+        match self {
+            RAMSize::None => 0,
+            RAMSize::KB2 => 2 * 1024,
+            RAMSize::KB8 => 8 * 1024,
+            RAMSize::KB32 => 32 * 1024,
+            RAMSize::KB64 => 64 * 1024,
+            RAMSize::KB128 => 128 * 1024,
+            RAMSize::Unknown(_) => 0,
+        }
+    }
+
+    /// The number of 8 KiB RAM banks. `KB2` counts as one partial bank.
+    pub fn num_banks(&self) -> usize {
+        self.bytes().div_ceil(RAM_BANK_BYTES)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-kebab-case", serde(rename_all = "kebab-case"))]
 pub enum DestinationCode {
     Japanese,
     NonJapanese,
+    /// A destination code not in the spec, only produced in lenient mode.
+    Unknown(u8),
 }
 
-#[derive(Serialize)]
+/// A guess at the market a ROM was released for, see `Header::likely_region`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-kebab-case", serde(rename_all = "kebab-case"))]
+pub enum Region {
+    Japan,
+    Usa,
+    Europe,
+    Unknown,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Header {
     pub entry_point: [u8; 4],
     pub logo_data: Vec<u8>, // len is always 48
+    /// Whether `logo_data` matches `NINTENDO_LOGO`, which the boot ROM checks.
+    pub logo_valid: bool,
+    /// The title up to the first `\0`, see `title_lossy` and `raw_title` for
+    /// the whole title area.
     pub game_title: String,
+    /// The unmodified title area at 0x134-0x143, including the manufacturer
+    /// code and CGB flag of CGB cartridges.
+    #[cfg_attr(feature = "std", serde(default))]
+    pub raw_title: [u8; 16],
+    /// The 4 byte code at 0x13F-0x142. Only meaningful for CGB cartridges,
+    /// for older cartridges this region is part of the title and the code
+    /// is left empty.
     pub manufacturer_code: String,
     pub gbc_flag: GBCFlag,
+    /// The raw byte at 0x143. For pre-CGB cartridges this is usually the
+    /// last character of the title.
+    #[cfg_attr(feature = "std", serde(default))]
+    pub gbc_flag_raw: u8,
     pub licensee_code: LicenseeCode,
+    /// The raw old licensee code at 0x14B, `0x33` if the new licensee code
+    /// is used instead.
+    #[cfg_attr(feature = "std", serde(default))]
+    pub raw_old_licensee_code: u8,
+    /// The raw new licensee code at 0x144-0x145. Only meaningful if
+    /// `raw_old_licensee_code` is `0x33`.
+    #[cfg_attr(feature = "std", serde(default))]
+    pub raw_new_licensee_code: [u8; 2],
     pub sgb_flag: SGBFlag,
     pub cartridge_type: CartridgeType,
     pub rom_size: ROMSize,
@@ -154,112 +1074,666 @@ pub struct Header {
     pub destination_code: DestinationCode,
     pub rom_version: u8,
     pub checksum: u8,
+    /// Whether `checksum` matches the checksum computed over 0x134-0x14C.
+    /// The boot ROM refuses to run a cartridge where this is false.
+    pub checksum_valid: bool,
+    /// The global checksum, stored big-endian at 0x14E-0x14F.
     pub global_checksum: u16,
+    /// Whether the stored global checksum matches the sum over the whole ROM.
+    /// Many homebrew ROMs get this wrong and real hardware does not check it.
+    pub global_checksum_valid: bool,
 }
 
+impl Header {
+    /// The raw header bytes in `HEADER_RANGE` of the ROM this header was
+    /// parsed from, clipped to the length of `data`.
+    pub fn raw_bytes<'a>(&self, data: &'a [u8]) -> &'a [u8] {
+        let end = HEADER_RANGE.end.min(data.len());
+        &data[HEADER_RANGE.start.min(end)..end]
+    }
+
+    /// The 16 bytes at 0x134-0x143 as stored in the ROM, for callers that
+    /// want to interpret the title region themselves, see also `full_title`.
+    pub fn raw_title_bytes(&self) -> [u8; 16] {
+        self.raw_title
+    }
+
+    /// The title read across the manufacturer code, for CGB cartridges whose
+    /// 11 byte title looks truncated: it fills all 11 bytes and the 4
+    /// manufacturer code bytes are printable ASCII that may continue it.
+    /// Returns `None` otherwise, including all pre-CGB cartridges where
+    /// `game_title` already spans the whole title area.
+    ///
+    /// Genuine manufacturer codes are printable ASCII as well, so whether the
+    /// 11 or 15 byte title is right is up to the caller.
+    pub fn full_title(&self) -> Option<String> {
+        let printable = |b: &u8| (b' '..=b'~').contains(b);
+        let truncated = self.gbc_flag != GBCFlag::GBOnly
+            && self.raw_title[10] != 0
+            && self.raw_title[11..15].iter().all(printable);
+        truncated.then(|| String::from_utf8_lossy(&self.raw_title[..15]).to_string())
+    }
+
+    /// The title as a lossy UTF-8 conversion with `\0` removed. Bytes that
+    /// are not valid UTF-8 become U+FFFD. Unlike `game_title` this keeps
+    /// the bytes after an embedded `\0`.
+    pub fn title_lossy(&self) -> String {
+        clean_string(&String::from_utf8_lossy(self.title_bytes()))
+    }
+
+    /// The title with every byte that is not printable ASCII removed, which
+    /// drops padding like 0xFF as well as control characters.
+    pub fn title_ascii(&self) -> String {
+        self.title_bytes()
+            .iter()
+            .filter(|b| (b' '..=b'~').contains(*b))
+            .map(|b| *b as char)
+            .collect()
+    }
+
+    /// Every header byte the parser could not map to a known value, keyed
+    /// by the field names of `field_offsets`, in header order. These are
+    /// the raw values behind the `Unknown` variants of lenient parsing, e.g.
+    /// `[("cartridge_type", 0x23)]`. An unknown new licensee code lists
+    /// both of its bytes.
+    pub fn unknown_bytes(&self) -> Vec<(&'static str, u8)> {
+        let mut unknown = Vec::new();
+        // lenient parsing maps unsupported CGB flags to GBOnly
+        if self.gbc_flag_raw & 0x80 != 0 && self.gbc_flag == GBCFlag::GBOnly {
+            unknown.push(("gbc_flag", self.gbc_flag_raw));
+        }
+        if let LicenseeCode::Unknown { new: Some(new), .. } = self.licensee_code {
+            unknown.extend(new.map(|b| ("new_licensee_code", b)));
+        }
+        if let SGBFlag::Unknown(v) = self.sgb_flag {
+            unknown.push(("sgb_flag", v));
+        }
+        if let CartridgeType::Unknown(v) = self.cartridge_type {
+            unknown.push(("cartridge_type", v));
+        }
+        if let ROMSize::Unknown(v) = self.rom_size {
+            unknown.push(("rom_size", v));
+        }
+        if let RAMSize::Unknown(v) = self.ram_size {
+            unknown.push(("ram_size", v));
+        }
+        if let DestinationCode::Unknown(v) = self.destination_code {
+            unknown.push(("destination_code", v));
+        }
+        if let LicenseeCode::Unknown { old: Some(old), .. } = self.licensee_code {
+            unknown.push(("old_licensee_code", old));
+        }
+        unknown
+    }
+
+    /// The offset in the ROM of every header field, keyed by field name.
+    /// `manufacturer_code` is only included for CGB cartridges. The licensee
+    /// can come from either of two bytes, both are listed as
+    /// `old_licensee_code` and `new_licensee_code`.
+    #[cfg(feature = "std")]
+    pub fn field_offsets(&self) -> HashMap<&'static str, usize> {
+        let mut offsets = HashMap::from([
+            ("entry_point", 0x100),
+            ("logo_data", 0x104),
+            ("game_title", 0x134),
+            ("gbc_flag", 0x143),
+            ("new_licensee_code", 0x144),
+            ("old_licensee_code", 0x14B),
+            ("sgb_flag", 0x146),
+            ("cartridge_type", 0x147),
+            ("rom_size", 0x148),
+            ("ram_size", 0x149),
+            ("destination_code", 0x14A),
+            ("rom_version", 0x14C),
+            ("checksum", HEADER_CHECKSUM_OFFSET),
+            ("global_checksum", GLOBAL_CHECKSUM_OFFSET),
+        ]);
+        if !matches!(self.gbc_flag, GBCFlag::GBOnly) {
+            offsets.insert("manufacturer_code", 0x13F);
+        }
+        offsets
+    }
+
+    /// The title the way it is shown to the user:
+    ///
+    /// - the title ends at the first `\0`, anything after it is padding
+    /// - bytes that are not printable ASCII are removed
+    /// - lowercase ASCII letters are uppercased
+    /// - trailing spaces are trimmed
+    pub fn display_title(&self) -> String {
+        let title: String = self
+            .title_bytes()
+            .iter()
+            .take_while(|b| **b != 0)
+            .filter(|b| (b' '..=b'~').contains(*b))
+            .map(|b| b.to_ascii_uppercase() as char)
+            .collect();
+        title.trim_end_matches(' ').to_string()
+    }
+
+    /// The stored header checksum minus the one computed from `data`.
+    ///
+    /// The checksum itself is a byte that wraps around, the delta does not:
+    /// both values are taken as 0-255, so a result of -255 and one of 1 can
+    /// stem from checksums that are equally close modulo 256.
+    ///
+    /// Bytes missing from a `data` too short to contain the header count as
+    /// 0, the same as for `global_checksum_delta`.
+    pub fn header_checksum_delta(&self, data: &[u8]) -> i16 {
+        let computed = HEADER_CHECKSUM_RANGE.fold(0u8, |x, i| {
+            let b = data.get(i).copied().unwrap_or(0);
+            x.wrapping_sub(b).wrapping_sub(1)
+        });
+        self.checksum as i16 - computed as i16
+    }
+
+    /// The stored global checksum minus the one computed from `data`, both
+    /// taken as 0-65535.
+    ///
+    /// Bytes missing from a short `data` count as 0, the same as for
+    /// `header_checksum_delta`.
+    pub fn global_checksum_delta(&self, data: &[u8]) -> i32 {
+        self.global_checksum as i32 - compute_global_checksum(data) as i32
+    }
+
+    // the part of the title area that holds the title, see `parse_header`
+    fn title_bytes(&self) -> &[u8] {
+        match self.gbc_flag {
+            GBCFlag::GBOnly => &self.raw_title,
+            GBCFlag::GBCAndGB | GBCFlag::GBCOnly => &self.raw_title[..11],
+        }
+    }
+
+    /// Whether the CGB boot ROM colorizes this game with a palette picked
+    /// for it, instead of the default palette used for other DMG games.
+    ///
+    /// This is the same check the CGB boot ROM performs: the game must not
+    /// be CGB aware, the licensee must be Nintendo and the sum of the 16
+    /// title bytes must be found in the boot ROM's table of title hashes.
+    /// The boot ROM uses the 4th title byte to pick between games with the
+    /// same hash, that only changes which palette is used.
+    ///
+    /// The licensee is checked on the raw bytes: only the old code 0x01 or
+    /// the new code "01" count, not the other codes `LicenseeCode::Nintendo`
+    /// is parsed from.
+    pub fn uses_builtin_palette(&self) -> bool {
+        let nintendo = match self.raw_old_licensee_code {
+            NEW_LICENCSEE_CODE_VAL => self.raw_new_licensee_code == *b"01",
+            old => old == 0x01,
+        };
+        if !matches!(self.gbc_flag, GBCFlag::GBOnly) || !nintendo {
+            return false;
+        }
+        let hash = self
+            .raw_title
+            .iter()
+            .fold(0u8, |sum, b| sum.wrapping_add(*b));
+        PALETTE_TITLE_HASHES.contains(&hash)
+    }
+
+    /// Classifies the logo, a refinement of `logo_valid` that tells a logo
+    /// only the CGB accepts from a corrupt one.
+    pub fn logo_kind(&self) -> LogoStatus {
+        if self.logo_data == NINTENDO_LOGO {
+            LogoStatus::Valid
+        } else if self.logo_data.get(..CGB_CHECKED_LOGO_BYTES)
+            == Some(&NINTENDO_LOGO[..CGB_CHECKED_LOGO_BYTES])
+        {
+            LogoStatus::NonStandard
+        } else {
+            LogoStatus::Corrupt
+        }
+    }
+
+    /// The destination of a `jp nn` (`C3 lo hi`) in the entry point, usually
+    /// preceded by a `nop`. Returns `None` for entry code without such a jump.
+    pub fn entry_jump_target(&self) -> Option<u16> {
+        self.entry_point
+            .windows(3)
+            .find(|w| w[0] == 0xC3)
+            .map(|w| u16::from_le_bytes([w[1], w[2]]))
+    }
+
+    /// Whether this is a revised release, i.e. the ROM version is not 0.
+    /// Rev A is version 1, Rev B version 2 and so on.
+    pub fn is_revised(&self) -> bool {
+        self.rom_version > 0
+    }
+
+    /// Whether the ROM only runs on a Game Boy Color.
+    pub fn is_cgb_only(&self) -> bool {
+        self.gbc_flag == GBCFlag::GBCOnly
+    }
+
+    /// Whether the ROM uses Game Boy Color features, with or without
+    /// falling back to the original Game Boy.
+    pub fn is_cgb_enhanced(&self) -> bool {
+        matches!(self.gbc_flag, GBCFlag::GBCAndGB | GBCFlag::GBCOnly)
+    }
+
+    /// Whether the ROM predates the Game Boy Color and knows nothing about it.
+    pub fn is_dmg_only(&self) -> bool {
+        self.gbc_flag == GBCFlag::GBOnly
+    }
+
+    /// Guesses the region the ROM was released in. This is a heuristic,
+    /// `destination_code` stays the authoritative value from the header:
+    /// - a Japanese destination code is always Japan
+    /// - otherwise the region letter at the end of a CGB manufacturer code
+    ///   (e.g. `E` in `AXVE`) decides
+    /// - otherwise European publishers suggest Europe, everything else
+    ///   with a non-Japanese destination code is assumed to be USA
+    pub fn likely_region(&self) -> Region {
+        if self.destination_code == DestinationCode::Japanese {
+            return Region::Japan;
+        }
+        match self.manufacturer_code.as_bytes().get(3) {
+            Some(b'J') => return Region::Japan,
+            Some(b'E') => return Region::Usa,
+            Some(b'P' | b'D' | b'F' | b'I' | b'S' | b'X' | b'Y') => return Region::Europe,
+            _ => {}
+        }
+        match (&self.destination_code, &self.licensee_code) {
+            (
+                _,
+                LicenseeCode::EliteSystems
+                | LicenseeCode::Gremlin
+                | LicenseeCode::Infogrames
+                | LicenseeCode::Ocean
+                | LicenseeCode::TheSalesCurve
+                | LicenseeCode::Titus
+                | LicenseeCode::UbiSoft
+                | LicenseeCode::USGold,
+            ) => Region::Europe,
+            (DestinationCode::NonJapanese, _) => Region::Usa,
+            _ => Region::Unknown,
+        }
+    }
+
+    /// Checks the cartridge type against the declared RAM size and the ROM
+    /// version for plausibility. The returned warnings point at suspicious
+    /// headers, they never fail the parse.
+    pub fn validate_consistency(&self) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+        let ram_declared = !matches!(self.ram_size, RAMSize::None);
+        let kind = self.cartridge_type.mbc_kind();
+
+        if self.rom_version > MAX_PLAUSIBLE_ROM_VERSION {
+            warnings.push(Warning::ImplausibleRomVersion(self.rom_version));
+        }
+
+        // no known licensed release uses these sizes, a header declaring them
+        // is more likely corrupt
+        if matches!(
+            self.rom_size,
+            ROMSize::Banks72 | ROMSize::Banks80 | ROMSize::Banks96
+        ) {
+            warnings.push(Warning::UnusualRomSize(self.rom_size.code()));
+        }
+
+        if self.is_cgb_only()
+            && self.destination_code == DestinationCode::Japanese
+            && is_pre_cgb_licensee(&self.licensee_code)
+        {
+            warnings.push(Warning::CgbFlagMismatch);
+        }
+
+        if kind == MbcKind::Mbc2 {
+            if ram_declared {
+                warnings.push(Warning::Mbc2ExternalRam);
+            }
+            return warnings;
+        }
+
+        if self.cartridge_type.has_ram() {
+            // MBC7 stores its data in an EEPROM that the RAM size does not cover
+            if !ram_declared && kind != MbcKind::Mbc7 {
+                if self.cartridge_type.has_battery() {
+                    warnings.push(Warning::BatteryWithoutRam);
+                } else {
+                    warnings.push(Warning::RamNotDeclared);
+                }
+            }
+        } else if ram_declared && kind != MbcKind::Unknown {
+            warnings.push(Warning::RamDeclaredWithoutRam);
+        }
+        warnings
+    }
+}
+
+/// How the logo at 0x104-0x133 compares to `NINTENDO_LOGO`, see
+/// `Header::logo_kind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-kebab-case", serde(rename_all = "kebab-case"))]
+pub enum LogoStatus {
+    /// The complete logo matches, the ROM boots on every model.
+    Valid,
+    /// Only the first `CGB_CHECKED_LOGO_BYTES` match. The CGB boot ROM
+    /// only checks those, so the ROM boots on a CGB but not on a DMG.
+    NonStandard,
+    /// The logo differs within the checked bytes, no boot ROM accepts it.
+    Corrupt,
+}
+
+/// The number of logo bytes the CGB boot ROM compares, the DMG boot ROM
+/// compares all 48.
+pub const CGB_CHECKED_LOGO_BYTES: usize = 24;
+
+/// The Nintendo logo bitmap every cartridge must contain at 0x104-0x133.
+pub const NINTENDO_LOGO: [u8; 48] = [
+    0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0C, 0x00, 0x0D,
+    0x00, 0x08, 0x11, 0x1F, 0x88, 0x89, 0x00, 0x0E, 0xDC, 0xCC, 0x6E, 0xE6, 0xDD, 0xDD, 0xD9, 0x99,
+    0xBB, 0xBB, 0x67, 0x63, 0x6E, 0x0E, 0xEC, 0xCC, 0xDD, 0xDC, 0x99, 0x9F, 0xBB, 0xB9, 0x33, 0x3E,
+];
+
+// Title hashes of the Nintendo games the CGB boot ROM has a palette for.
+const PALETTE_TITLE_HASHES: [u8; 78] = [
+    0x88, 0x16, 0x36, 0xD1, 0xDB, 0xF2, 0x3C, 0x8C, 0x92, 0x3D, 0x5C, 0x58, 0xC9, 0x3E, 0x70, 0x1D,
+    0x59, 0x69, 0x19, 0x35, 0xA8, 0x14, 0xAA, 0x75, 0x95, 0x99, 0x34, 0x6F, 0x15, 0xFF, 0x97, 0x4B,
+    0x90, 0x17, 0x10, 0x39, 0xF7, 0xF6, 0xA2, 0x49, 0x4E, 0x43, 0x68, 0xE0, 0x8B, 0xF0, 0xCE, 0x0C,
+    0x29, 0xE8, 0xB7, 0x86, 0x9A, 0x52, 0x01, 0x9D, 0x71, 0x9C, 0xBD, 0x5D, 0x6D, 0x67, 0x3F, 0x6B,
+    0xB3, 0x46, 0x28, 0xA5, 0xC6, 0xD3, 0x27, 0x61, 0x18, 0x66, 0x6A, 0xBF, 0x0D, 0xF4,
+];
+
 pub const NEW_LICENCSEE_CODE_VAL: u8 = 0x33;
+/// Licensed releases rarely got more than a few revisions, anything above
+/// this is more likely garbage than a real revision.
+pub const MAX_PLAUSIBLE_ROM_VERSION: u8 = 0x0F;
 pub const BANK_BYTES: usize = 16 * 1024;
 pub const DATA_START: usize = 0x150;
+pub const HEADER_CHECKSUM_OFFSET: usize = 0x14D;
+pub const GLOBAL_CHECKSUM_OFFSET: usize = 0x14E;
+/// The cartridge header from the entry point to the global checksum.
+pub const HEADER_RANGE: Range<usize> = 0x100..DATA_START;
+/// The bytes the header checksum at 0x14D is computed over.
+pub const HEADER_CHECKSUM_RANGE: RangeInclusive<usize> = 0x134..=0x14C;
+
+/// Controls how tolerant the parser is. The default is lenient: unknown
+/// header values are mapped to `Unknown` variants and checksums are only
+/// reported, not enforced.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct LoadOptions {
+    /// Fail on header values that are not in the spec instead of mapping
+    /// them to `Unknown` variants.
+    pub strict: bool,
+    /// Fail if the header or global checksum does not match.
+    pub verify_checksums: bool,
+    /// Accept ROMs whose length differs from the declared ROM size. The
+    /// number of banks is then inferred from the length of the data, see
+    /// `GBBinary::inferred_banks`.
+    pub allow_size_mismatch: bool,
+}
+
+pub fn load(data: &[u8]) -> Result<GBBinary, GBError> {
+    load_with_options(data, LoadOptions::default())
+}
 
-pub fn load(data: &[u8]) -> Result<GBBinary, String> {
+pub fn load_with_options(data: &[u8], opts: LoadOptions) -> Result<GBBinary, GBError> {
+    check_header_len(data)?;
     let mut reader = DataReader::new(data);
     let restart_calls = parse_restart_calls(&mut reader)?;
     let interrupts = parse_interrupts(&mut reader)?;
-    let header = parse_header(&mut reader)?;
-    let bank_data = parse_bank_data(&mut reader, header.rom_size)?;
+    let header = parse_header(&mut reader, &opts)?;
+
+    if opts.verify_checksums {
+        verify_checksums(data, &header)?;
+    }
+
+    check_rom_size(header.rom_size, data.len(), &opts)?;
+
+    let declared_banks = header.rom_size.num_banks();
+    let bank_data = if opts.allow_size_mismatch {
+        // the bank count follows the data, a partial last bank is padded
+        // with 0xFF like unused ROM space
+        let inferred_banks = data.len().div_ceil(BANK_BYTES);
+        if data.len().is_multiple_of(BANK_BYTES) {
+            parse_bank_data(data, inferred_banks)?
+        } else {
+            let mut padded = data.to_vec();
+            padded.resize(inferred_banks * BANK_BYTES, 0xFF);
+            parse_bank_data(&padded, inferred_banks)?
+        }
+    } else {
+        parse_bank_data(data, declared_banks)?
+    };
 
     Ok(GBBinary {
         restart_calls,
         interrupts,
         header,
+        inferred_banks: bank_data.len(),
         bank_data,
+        declared_banks,
     })
 }
 
-fn parse_restart_calls(reader: &mut DataReader) -> Result<RestartCalls, String> {
+/// Loads a ROM that is embedded in a larger blob, e.g. a flashcart menu
+/// bundle, starting at `base`. All offsets, including the ones in errors,
+/// are relative to `base`. The ROM ends where its declared ROM size says,
+/// data following it is ignored.
+pub fn load_at_offset(data: &[u8], base: usize) -> Result<GBBinary, GBError> {
+    let rom = data.get(base..).ok_or(GBError::TooShort { len: 0 })?;
+    let header = load_header(rom)?;
+    let end = match header.rom_size.bytes() {
+        0 => rom.len(),
+        declared => declared.min(rom.len()),
+    };
+    load(&rom[..end])
+}
+
+/// Loads a ROM from any reader. The complete ROM is buffered in memory as
+/// the parser needs random access to it.
+#[cfg(feature = "std")]
+pub fn load_from_reader<R: Read>(mut r: R) -> Result<GBBinary, GBError> {
+    let mut buf = Vec::new();
+    r.read_to_end(&mut buf)?;
+    load(&buf)
+}
+
+/// Parses only the header, without copying any bank data.
+pub fn load_header(data: &[u8]) -> Result<Header, GBError> {
+    check_header_len(data)?;
+    let mut reader = DataReader::new(data);
+    parse_restart_calls(&mut reader)?;
+    parse_interrupts(&mut reader)?;
+    parse_header(&mut reader, &LoadOptions::default())
+}
+
+/// Copies a single bank out of the ROM without allocating the others. The
+/// index is checked against the number of banks the header declares.
+pub fn load_bank(data: &[u8], index: usize) -> Result<Vec<u8>, GBError> {
+    let header = load_header(data)?;
+    let banks = header.rom_size.num_banks();
+    if index >= banks {
+        return Err(GBError::BankOutOfRange { index, banks });
+    }
+    let start = index * BANK_BYTES;
+    data.get(start..start + BANK_BYTES)
+        .map(<[u8]>::to_vec)
+        .ok_or(GBError::NotEnoughBankData {
+            expected: start + BANK_BYTES,
+            actual: data.len(),
+        })
+}
+
+fn verify_checksums(data: &[u8], header: &Header) -> Result<(), GBError> {
+    if !header.checksum_valid {
+        return Err(GBError::HeaderChecksumMismatch {
+            stored: header.checksum,
+            computed: computed_header_checksum(data),
+        });
+    }
+    if !header.global_checksum_valid {
+        return Err(GBError::GlobalChecksumMismatch {
+            stored: header.global_checksum,
+            computed: compute_global_checksum(data),
+        });
+    }
+    Ok(())
+}
+
+// Checks the length of the data against the declared ROM size, unless the
+// options allow a mismatch. An unknown ROM size declares no length to check
+// against and is rejected as such.
+fn check_rom_size(rom_size: ROMSize, len: usize, opts: &LoadOptions) -> Result<(), GBError> {
+    if opts.allow_size_mismatch {
+        return Ok(());
+    }
+    if let ROMSize::Unknown(value) = rom_size {
+        return Err(GBError::UnsupportedRomSize {
+            value,
+            offset: 0x148,
+        });
+    }
+    let declared = rom_size.bytes();
+    if len != declared {
+        return Err(GBError::SizeMismatch {
+            declared,
+            actual: len,
+        });
+    }
+    Ok(())
+}
+
+// The header ends at DATA_START, anything shorter cannot be a ROM.
+fn check_header_len(data: &[u8]) -> Result<(), GBError> {
+    if data.len() < DATA_START {
+        return Err(GBError::TooShort { len: data.len() });
+    }
+    Ok(())
+}
+
+fn parse_restart_calls(reader: &mut DataReader) -> Result<RestartCalls, GBError> {
     let calls = RestartCalls {
-        rst_0: read_8_bytes(reader),
-        rst_1: read_8_bytes(reader),
-        rst_2: read_8_bytes(reader),
-        rst_3: read_8_bytes(reader),
-        rst_4: read_8_bytes(reader),
-        rst_5: read_8_bytes(reader),
-        rst_6: read_8_bytes(reader),
-        rst_7: read_8_bytes(reader),
+        rst_0: read_8_bytes(reader)?,
+        rst_1: read_8_bytes(reader)?,
+        rst_2: read_8_bytes(reader)?,
+        rst_3: read_8_bytes(reader)?,
+        rst_4: read_8_bytes(reader)?,
+        rst_5: read_8_bytes(reader)?,
+        rst_6: read_8_bytes(reader)?,
+        rst_7: read_8_bytes(reader)?,
     };
     Ok(calls)
 }
 
-fn parse_interrupts(reader: &mut DataReader) -> Result<Interrupts, String> {
+fn parse_interrupts(reader: &mut DataReader) -> Result<Interrupts, GBError> {
     let interrupts = Interrupts {
-        v_blank: read_8_bytes(reader),
-        lcd_stat: read_8_bytes(reader),
-        timer: read_8_bytes(reader),
-        serial: read_8_bytes(reader),
-        joypad: read_8_bytes(reader),
+        v_blank: read_8_bytes(reader)?,
+        lcd_stat: read_8_bytes(reader)?,
+        timer: read_8_bytes(reader)?,
+        serial: read_8_bytes(reader)?,
+        joypad: read_8_bytes(reader)?,
     };
 
     reader.skip(0x98);
     Ok(interrupts)
 }
 
-fn read_8_bytes(reader: &mut DataReader) -> [u8; 8] {
+fn read_8_bytes(reader: &mut DataReader) -> Result<[u8; 8], GBError> {
     let mut result = [0; 8];
-    for i in 0..8 {
-        result[i] = reader.read_u8();
+    for b in result.iter_mut() {
+        *b = reader.try_read_u8()?;
     }
-    result
+    Ok(result)
 }
 
-fn parse_header(reader: &mut DataReader) -> Result<Header, String> {
-    let entry_point = [
-        reader.read_u8(),
-        reader.read_u8(),
-        reader.read_u8(),
-        reader.read_u8(),
-    ];
-    let mut logo_data = vec![0; 48];
-    for i in 0..48 {
-        logo_data[i] = reader.read_u8();
-    }
+fn parse_header(reader: &mut DataReader, opts: &LoadOptions) -> Result<Header, GBError> {
+    parse_header_with(reader, opts, &mut |_| {})
+}
 
-    let old_licensee_code = reader.read_u8_at(0x14B);
+// Parses the header and reports every field to `emit` as soon as it is
+// read, see `parse_streaming`.
+fn parse_header_with(
+    reader: &mut DataReader,
+    opts: &LoadOptions,
+    emit: &mut dyn FnMut(ParseEvent),
+) -> Result<Header, GBError> {
+    let entry_point: [u8; 4] = reader.try_read_bytes(4)?.try_into().unwrap();
+    let logo_data = reader.try_read_bytes(48)?.to_vec();
+    let logo_valid = logo_data == NINTENDO_LOGO;
+    emit(ParseEvent::EntryPoint(entry_point));
+    emit(ParseEvent::Logo { valid: logo_valid });
 
-    let game_title = if old_licensee_code == NEW_LICENCSEE_CODE_VAL {
-        clean_string(&reader.read_utf8_string(11))
-    } else {
-        clean_string(&reader.read_utf8_string(15))
-    };
+    let old_licensee_code = reader.try_read_u8_at(0x14B).ok_or(GBError::UnexpectedEof {
+        offset: 0x14B,
+        needed: 1,
+    })?;
+    let raw_title: [u8; 16] = reader
+        .try_peek_bytes(16)
+        .and_then(|b| b.try_into().ok())
+        .ok_or(GBError::UnexpectedEof {
+            offset: reader.offset(),
+            needed: 16,
+        })?;
 
-    let mut manufacturer_code = "".to_string();
-    if old_licensee_code == NEW_LICENCSEE_CODE_VAL {
-        manufacturer_code = clean_string(&reader.read_utf8_string(4));
-    }
+    // The title area is 16 bytes. CGB aware cartridges split it into an
+    // 11 byte title, a 4 byte manufacturer code and the CGB flag.
+    let gbc_flag_raw = reader.try_read_u8_at(0x143).ok_or(GBError::UnexpectedEof {
+        offset: 0x143,
+        needed: 1,
+    })?;
+    let gbc_flag = parse_gbc_flag(gbc_flag_raw, opts.strict)?;
+    let (game_title, manufacturer_code) = match gbc_flag {
+        GBCFlag::GBOnly => (reader.try_read_utf8_string_trimmed(16)?, "".to_string()),
+        GBCFlag::GBCAndGB | GBCFlag::GBCOnly => {
+            let title = reader.try_read_utf8_string_trimmed(11)?;
+            let manufacturer_code = reader.try_read_utf8_string_trimmed(4)?;
+            reader.skip(1); // CGB flag already read above
+            (title, manufacturer_code)
+        }
+    };
+    emit(ParseEvent::Title(game_title.clone()));
+    emit(ParseEvent::ManufacturerCode(manufacturer_code.clone()));
+    emit(ParseEvent::GbcFlag(gbc_flag));
 
-    let gbc_flag = parse_gbc_flag(reader.read_u8())?;
-    let new_licensee_code = [reader.read_u8(), reader.read_u8()];
+    let new_licensee_code = [reader.try_read_u8()?, reader.try_read_u8()?];
     let licensee_code = if old_licensee_code == NEW_LICENCSEE_CODE_VAL {
         parse_new_licensee_code(&new_licensee_code)
     } else {
         parse_old_licensee_code(old_licensee_code)
     };
-    let sgb_flag = parse_sgb_flag(reader.read_u8())?;
-    let cartridge_type = parse_cartridge_type(reader.read_u8())?;
-    let rom_size = parse_rom_size(reader.read_u8())?;
-    let ram_size = parse_ram_size(reader.read_u8())?;
-    let destination_code = parse_destination_code(reader.read_u8())?;
-    reader.skip(1); // old licensee code already read above
-    let rom_version = reader.read_u8();
-    let checksum = reader.read_u8();
-    let global_checksum = reader.read_u16();
+    emit(ParseEvent::LicenseeCode(licensee_code.clone()));
+    let sgb_flag = parse_sgb_flag(reader, opts.strict)?;
+    emit(ParseEvent::SgbFlag(sgb_flag));
+    let cartridge_type = parse_cartridge_type(reader, opts.strict)?;
+    emit(ParseEvent::CartridgeType(cartridge_type));
+    let rom_size = parse_rom_size(reader, opts.strict)?;
+    emit(ParseEvent::RomSize(rom_size));
+    let ram_size = parse_ram_size(reader, opts.strict)?;
+    emit(ParseEvent::RamSize(ram_size));
+    let destination_code = parse_destination_code(reader, opts.strict)?;
+    emit(ParseEvent::DestinationCode(destination_code));
+    reader.seek(0x14C); // old licensee code at 0x14B already read above
+    let rom_version = reader.try_read_u8()?;
+    emit(ParseEvent::RomVersion(rom_version));
+    let checksum = reader.try_read_u8()?;
+    let checksum_valid =
+        checksum == computed_header_checksum(reader.slice(0, HEADER_CHECKSUM_OFFSET));
+    emit(ParseEvent::HeaderChecksum {
+        stored: checksum,
+        valid: checksum_valid,
+    });
+    let global_checksum = reader.try_read_u16_be()?;
+    let global_checksum_valid = global_checksum_valid(reader.data());
+    emit(ParseEvent::GlobalChecksum {
+        stored: global_checksum,
+        valid: global_checksum_valid,
+    });
 
     Ok(Header {
         entry_point,
         logo_data,
+        logo_valid,
         game_title,
+        raw_title,
         manufacturer_code,
         gbc_flag,
+        gbc_flag_raw,
         licensee_code,
+        raw_old_licensee_code: old_licensee_code,
+        raw_new_licensee_code: new_licensee_code,
         sgb_flag,
         cartridge_type,
         rom_size,
@@ -267,96 +1741,201 @@ fn parse_header(reader: &mut DataReader) -> Result<Header, String> {
         destination_code,
         rom_version,
         checksum,
+        checksum_valid,
         global_checksum,
+        global_checksum_valid,
     })
 }
 
-fn parse_gbc_flag(flag: u8) -> Result<GBCFlag, String> {
+/// Computes the header checksum over the bytes 0x134-0x14C (inclusive) the
+/// same way the boot ROM does. Panics if `data` is shorter than 0x14D bytes.
+pub fn computed_header_checksum(data: &[u8]) -> u8 {
+    let mut x: u8 = 0;
+    for b in &data[HEADER_CHECKSUM_RANGE] {
+        x = x.wrapping_sub(*b).wrapping_sub(1);
+    }
+    x
+}
+
+/// Checks the header checksum byte at 0x14D against the computed checksum.
+/// Returns false if `data` is too short to contain the checksum.
+pub fn verify_header_checksum(data: &[u8]) -> bool {
+    if data.len() <= HEADER_CHECKSUM_OFFSET {
+        return false;
+    }
+    data[HEADER_CHECKSUM_OFFSET] == computed_header_checksum(data)
+}
+
+/// Computes the global checksum: the wrapping 16-bit sum of all bytes in the
+/// ROM except the two checksum bytes at 0x14E-0x14F.
+pub fn compute_global_checksum(data: &[u8]) -> u16 {
+    let mut sum: u16 = 0;
+    for (i, b) in data.iter().enumerate() {
+        if i == GLOBAL_CHECKSUM_OFFSET || i == GLOBAL_CHECKSUM_OFFSET + 1 {
+            continue;
+        }
+        sum = sum.wrapping_add(*b as u16);
+    }
+    sum
+}
+
+/// Checks the global checksum stored (big-endian) at 0x14E-0x14F against the
+/// computed one. Returns false if `data` is too short to contain the checksum.
+pub fn global_checksum_valid(data: &[u8]) -> bool {
+    if data.len() <= GLOBAL_CHECKSUM_OFFSET + 1 {
+        return false;
+    }
+    let stored = u16::from_be_bytes([
+        data[GLOBAL_CHECKSUM_OFFSET],
+        data[GLOBAL_CHECKSUM_OFFSET + 1],
+    ]);
+    stored == compute_global_checksum(data)
+}
+
+/// Recomputes the header checksum at 0x14D and the global checksum at
+/// 0x14E-0x14F in place. Does nothing if `data` is too short to contain
+/// the checksums.
+pub fn fix_checksums(data: &mut [u8]) {
+    if data.len() <= GLOBAL_CHECKSUM_OFFSET + 1 {
+        return;
+    }
+    // the global checksum includes the header checksum, it has to be fixed first
+    data[HEADER_CHECKSUM_OFFSET] = computed_header_checksum(data);
+    let global_checksum = compute_global_checksum(data);
+    data[GLOBAL_CHECKSUM_OFFSET..GLOBAL_CHECKSUM_OFFSET + 2]
+        .copy_from_slice(&global_checksum.to_be_bytes());
+}
+
+// Pre-CGB cartridges use 0x143 as the last byte of the title, so any value
+// other than 0x80 or 0xC0 means the cartridge is not CGB aware. Strict mode
+// rejects other values with bit 7 set, as those are neither title characters
+// nor a known flag.
+fn parse_gbc_flag(flag: u8, strict: bool) -> Result<GBCFlag, GBError> {
     match flag {
-        0 => Ok(GBCFlag::GBOnly),
         0x80 => Ok(GBCFlag::GBCAndGB),
         0xC0 => Ok(GBCFlag::GBCOnly),
-        _ => Err(format!("unsupported GBC flag: {:x}", flag)),
+        _ if strict && flag & 0x80 != 0 => Err(GBError::UnsupportedGbcFlag {
+            value: flag,
+            offset: 0x143,
+        }),
+        _ => Ok(GBCFlag::GBOnly),
     }
 }
 
-fn parse_sgb_flag(flag: u8) -> Result<SGBFlag, String> {
+fn parse_sgb_flag(reader: &mut DataReader, strict: bool) -> Result<SGBFlag, GBError> {
+    let offset = reader.offset();
+    let flag = reader.try_read_u8()?;
     match flag {
         0x00 => Ok(SGBFlag::NoSGB),
         0x03 => Ok(SGBFlag::SGBSupport),
-        _ => Err(format!("unsupported SGB flag: {:x}", flag)),
-    }
-}
-
-fn parse_cartridge_type(t: u8) -> Result<CartridgeType, String> {
-    match t {
-        0x00 => Ok(CartridgeType::ROMOnly),
-        0x01 => Ok(CartridgeType::MBC1),
-        0x02 => Ok(CartridgeType::MBC1xRAM),
-        0x03 => Ok(CartridgeType::MBC1xRAMxBattery),
-        0x05 => Ok(CartridgeType::MBC2),
-        0x06 => Ok(CartridgeType::MBC2xBattery),
-        0x08 => Ok(CartridgeType::ROMxRAM),
-        0x09 => Ok(CartridgeType::ROMxRAMxBattery),
-        0x0B => Ok(CartridgeType::MMM01),
-        0x0C => Ok(CartridgeType::MMM01xRAM),
-        0x0D => Ok(CartridgeType::MMM01xRAMxBattery),
-        0x0F => Ok(CartridgeType::MBC3xTimerxBattery),
-        0x10 => Ok(CartridgeType::MBC3xTimerxRAMxBattery),
-        0x11 => Ok(CartridgeType::MBC3),
-        0x12 => Ok(CartridgeType::MBC3xRAM),
-        0x13 => Ok(CartridgeType::MBC1xRAMxBattery),
-        0x19 => Ok(CartridgeType::MBC5),
-        0x1A => Ok(CartridgeType::MBC5xRAM),
-        0x1B => Ok(CartridgeType::MBC5xRAMxBattery),
-        0x1C => Ok(CartridgeType::MBC5xRumble),
-        0x1D => Ok(CartridgeType::MBC5xRumblexRAM),
-        0x1E => Ok(CartridgeType::MBC5xRumblexRAMxBattery),
-        0x20 => Ok(CartridgeType::MBC6),
-        0x22 => Ok(CartridgeType::MBC7xSensorxRumblexRAMxBattery),
-        0xFC => Ok(CartridgeType::PocketCamera),
-        0xFD => Ok(CartridgeType::BandaiTama5),
-        0xFE => Ok(CartridgeType::HuC3),
-        0xFF => Ok(CartridgeType::HuC1xRAMxBattery),
-        _ => Err(format!("unsuported cartridge type: {:x}", t)),
-    }
-}
-
-fn parse_rom_size(v: u8) -> Result<ROMSize, String> {
-    match v {
-        0x00 => Ok(ROMSize::NoBanking),
-        0x01 => Ok(ROMSize::Banks4),
-        0x02 => Ok(ROMSize::Banks8),
-        0x03 => Ok(ROMSize::Banks16),
-        0x04 => Ok(ROMSize::Banks32),
-        0x05 => Ok(ROMSize::Banks64),
-        0x06 => Ok(ROMSize::Banks128),
-        0x07 => Ok(ROMSize::Banks256),
-        0x08 => Ok(ROMSize::Banks512),
-        0x52 => Ok(ROMSize::Banks72),
-        0x53 => Ok(ROMSize::Banks80),
-        0x54 => Ok(ROMSize::Banks96),
-        _ => Err(format!("unsupported rom size: {:x}", v)),
-    }
-}
-
-fn parse_ram_size(v: u8) -> Result<RAMSize, String> {
-    match v {
-        0x00 => Ok(RAMSize::None),
-        0x01 => Ok(RAMSize::KB2),
-        0x02 => Ok(RAMSize::KB8),
-        0x03 => Ok(RAMSize::KB32),
-        0x04 => Ok(RAMSize::KB128),
-        0x05 => Ok(RAMSize::KB64),
-        _ => Err(format!("unsupported ram size: {:x}", v)),
+        _ if strict => Err(GBError::UnsupportedSgbFlag {
+            value: flag,
+            offset,
+        }),
+        _ => Ok(SGBFlag::Unknown(flag)),
+    }
+}
+
+/// Every cartridge type byte defined by the spec and the type it stands for.
+pub const CARTRIDGE_TYPES: &[(u8, CartridgeType)] = &[
+    (0x00, CartridgeType::ROMOnly),
+    (0x01, CartridgeType::MBC1),
+    (0x02, CartridgeType::MBC1xRAM),
+    (0x03, CartridgeType::MBC1xRAMxBattery),
+    (0x05, CartridgeType::MBC2),
+    (0x06, CartridgeType::MBC2xBattery),
+    (0x08, CartridgeType::ROMxRAM),
+    (0x09, CartridgeType::ROMxRAMxBattery),
+    (0x0B, CartridgeType::MMM01),
+    (0x0C, CartridgeType::MMM01xRAM),
+    (0x0D, CartridgeType::MMM01xRAMxBattery),
+    (0x0F, CartridgeType::MBC3xTimerxBattery),
+    (0x10, CartridgeType::MBC3xTimerxRAMxBattery),
+    (0x11, CartridgeType::MBC3),
+    (0x12, CartridgeType::MBC3xRAM),
+    (0x13, CartridgeType::MBC3xRAMxBattery),
+    (0x19, CartridgeType::MBC5),
+    (0x1A, CartridgeType::MBC5xRAM),
+    (0x1B, CartridgeType::MBC5xRAMxBattery),
+    (0x1C, CartridgeType::MBC5xRumble),
+    (0x1D, CartridgeType::MBC5xRumblexRAM),
+    (0x1E, CartridgeType::MBC5xRumblexRAMxBattery),
+    (0x20, CartridgeType::MBC6),
+    (0x22, CartridgeType::MBC7xSensorxRumblexRAMxBattery),
+    (0xFC, CartridgeType::PocketCamera),
+    (0xFD, CartridgeType::BandaiTama5),
+    (0xFE, CartridgeType::HuC3),
+    (0xFF, CartridgeType::HuC1xRAMxBattery),
+];
+
+fn parse_cartridge_type(reader: &mut DataReader, strict: bool) -> Result<CartridgeType, GBError> {
+    let offset = reader.offset();
+    let t = reader.try_read_u8()?;
+    match CARTRIDGE_TYPES.iter().find(|(code, _)| *code == t) {
+        Some((_, value)) => Ok(*value),
+        None if strict => Err(GBError::UnsupportedCartridgeType { value: t, offset }),
+        None => Ok(CartridgeType::Unknown(t)),
+    }
+}
+
+/// Every ROM size byte defined by the spec and the size it stands for.
+pub const ROM_SIZES: &[(u8, ROMSize)] = &[
+    (0x00, ROMSize::NoBanking),
+    (0x01, ROMSize::Banks4),
+    (0x02, ROMSize::Banks8),
+    (0x03, ROMSize::Banks16),
+    (0x04, ROMSize::Banks32),
+    (0x05, ROMSize::Banks64),
+    (0x06, ROMSize::Banks128),
+    (0x07, ROMSize::Banks256),
+    (0x08, ROMSize::Banks512),
+    (0x52, ROMSize::Banks72),
+    (0x53, ROMSize::Banks80),
+    (0x54, ROMSize::Banks96),
+];
+
+fn parse_rom_size(reader: &mut DataReader, strict: bool) -> Result<ROMSize, GBError> {
+    let offset = reader.offset();
+    let v = reader.try_read_u8()?;
+    match ROM_SIZES.iter().find(|(code, _)| *code == v) {
+        Some((_, value)) => Ok(*value),
+        None if strict => Err(GBError::UnsupportedRomSize { value: v, offset }),
+        None => Ok(ROMSize::Unknown(v)),
+    }
+}
+
+/// Every RAM size byte defined by the spec and the size it stands for.
+/// Note 0x04 is 128 KiB and 0x05 is 64 KiB, see `RAMSize`.
+pub const RAM_SIZES: &[(u8, RAMSize)] = &[
+    (0x00, RAMSize::None),
+    (0x01, RAMSize::KB2),
+    (0x02, RAMSize::KB8),
+    (0x03, RAMSize::KB32),
+    (0x04, RAMSize::KB128),
+    (0x05, RAMSize::KB64),
+];
+
+fn parse_ram_size(reader: &mut DataReader, strict: bool) -> Result<RAMSize, GBError> {
+    let offset = reader.offset();
+    let v = reader.try_read_u8()?;
+    match RAM_SIZES.iter().find(|(code, _)| *code == v) {
+        Some((_, value)) => Ok(*value),
+        None if strict => Err(GBError::UnsupportedRamSize { value: v, offset }),
+        None => Ok(RAMSize::Unknown(v)),
     }
 }
 
-fn parse_destination_code(v: u8) -> Result<DestinationCode, String> {
+fn parse_destination_code(
+    reader: &mut DataReader,
+    strict: bool,
+) -> Result<DestinationCode, GBError> {
+    let offset = reader.offset();
+    let v = reader.try_read_u8()?;
     match v {
         0x00 => Ok(DestinationCode::Japanese),
         0x01 => Ok(DestinationCode::NonJapanese),
-        _ => Err(format!("unsupported destination code: {:x}", v)),
+        _ if strict => Err(GBError::UnsupportedDestinationCode { value: v, offset }),
+        _ => Ok(DestinationCode::Unknown(v)),
     }
 }
 
@@ -365,54 +1944,589 @@ fn parse_new_licensee_code(code: &[u8; 2]) -> LicenseeCode {
         b"00" => LicenseeCode::None,
         b"01" => LicenseeCode::Nintendo,
         b"08" => LicenseeCode::Capcom,
-        b"B2" => LicenseeCode::Bandai,
-        b"AF" => LicenseeCode::Namco,
-        _ => LicenseeCode::Unknown,
+        b"13" => LicenseeCode::ElectronicArts,
+        b"18" => LicenseeCode::HudsonSoft,
+        b"19" => LicenseeCode::BAi,
+        b"20" => LicenseeCode::Kss,
+        b"22" => LicenseeCode::PlanningOfficeWada,
+        b"24" => LicenseeCode::PCMComplete,
+        b"25" => LicenseeCode::SanX,
+        b"28" => LicenseeCode::Kemco,
+        b"29" => LicenseeCode::Seta,
+        b"30" => LicenseeCode::Viacom,
+        b"31" => LicenseeCode::Nintendo,
+        b"32" => LicenseeCode::Bandai,
+        b"33" => LicenseeCode::OceanAcclaim,
+        b"34" => LicenseeCode::Konami,
+        b"35" => LicenseeCode::HectorSoft,
+        b"37" => LicenseeCode::Taito,
+        b"38" => LicenseeCode::HudsonSoft,
+        b"39" => LicenseeCode::Banpresto,
+        b"41" => LicenseeCode::UbiSoft,
+        b"42" => LicenseeCode::Atlus,
+        b"44" => LicenseeCode::MalibuInteractive,
+        b"46" => LicenseeCode::Angel,
+        b"47" => LicenseeCode::BulletProofSoftware,
+        b"49" => LicenseeCode::Irem,
+        b"50" => LicenseeCode::Absolute,
+        b"51" => LicenseeCode::Acclaim,
+        b"52" => LicenseeCode::Activision,
+        b"53" => LicenseeCode::SammyUSA,
+        b"54" => LicenseeCode::Konami,
+        b"55" => LicenseeCode::HiTechExpressions,
+        b"56" => LicenseeCode::Ljn,
+        b"57" => LicenseeCode::Matchbox,
+        b"58" => LicenseeCode::Mattel,
+        b"59" => LicenseeCode::MiltonBradley,
+        b"60" => LicenseeCode::Titus,
+        b"61" => LicenseeCode::Virgin,
+        b"64" => LicenseeCode::LucasfilmGames,
+        b"67" => LicenseeCode::Ocean,
+        b"69" => LicenseeCode::ElectronicArts,
+        b"70" => LicenseeCode::Infogrames,
+        b"71" => LicenseeCode::Interplay,
+        b"72" => LicenseeCode::Broderbund,
+        b"73" => LicenseeCode::SculpturedSoftware,
+        b"75" => LicenseeCode::TheSalesCurve,
+        b"78" => LicenseeCode::Thq,
+        b"79" => LicenseeCode::Accolade,
+        b"80" => LicenseeCode::MisawaEntertainment,
+        b"83" => LicenseeCode::Lozc,
+        b"86" => LicenseeCode::TokumaShoten,
+        b"87" => LicenseeCode::TsukudaOriginal,
+        b"91" => LicenseeCode::Chunsoft,
+        b"92" => LicenseeCode::VideoSystem,
+        b"93" => LicenseeCode::OceanAcclaim,
+        b"95" => LicenseeCode::Varie,
+        b"96" => LicenseeCode::YonezawaSPal,
+        b"97" => LicenseeCode::Kaneko,
+        b"99" => LicenseeCode::PackInVideo,
+        b"9H" => LicenseeCode::BottomUp,
+        b"A4" => LicenseeCode::KonamiYuGiOh,
+        b"BL" => LicenseeCode::Mto,
+        b"DK" => LicenseeCode::Kodansha,
+        _ => LicenseeCode::Unknown {
+            old: None,
+            new: Some(*code),
+        },
     }
-    // TODO complete this mapping
 }
 
+// 0x33 (NEW_LICENCSEE_CODE_VAL) is not part of this table, the licensee is
+// then taken from the new licensee code instead.
 fn parse_old_licensee_code(code: u8) -> LicenseeCode {
     match code {
-        0x0 => LicenseeCode::None,
+        0x00 => LicenseeCode::None,
         0x01 => LicenseeCode::Nintendo,
         0x08 => LicenseeCode::Capcom,
-        0xB2 => LicenseeCode::Bandai,
+        0x09 => LicenseeCode::HotB,
+        0x0A => LicenseeCode::Jaleco,
+        0x0B => LicenseeCode::CoconutsJapan,
+        0x0C => LicenseeCode::EliteSystems,
+        0x13 => LicenseeCode::ElectronicArts,
+        0x18 => LicenseeCode::HudsonSoft,
+        0x19 => LicenseeCode::ITCEntertainment,
+        0x1A => LicenseeCode::Yanoman,
+        0x1D => LicenseeCode::JapanClary,
+        0x1F => LicenseeCode::Virgin,
+        0x24 => LicenseeCode::PCMComplete,
+        0x25 => LicenseeCode::SanX,
+        0x28 => LicenseeCode::Kemco,
+        0x29 => LicenseeCode::Seta,
+        0x30 => LicenseeCode::Infogrames,
+        0x31 => LicenseeCode::Nintendo,
+        0x32 => LicenseeCode::Bandai,
+        0x34 => LicenseeCode::Konami,
+        0x35 => LicenseeCode::HectorSoft,
+        0x38 => LicenseeCode::Capcom,
+        0x39 => LicenseeCode::Banpresto,
+        0x3C => LicenseeCode::EntertainmentInteractive,
+        0x3E => LicenseeCode::Gremlin,
+        0x41 => LicenseeCode::UbiSoft,
+        0x42 => LicenseeCode::Atlus,
+        0x44 => LicenseeCode::MalibuInteractive,
+        0x46 => LicenseeCode::Angel,
+        0x47 => LicenseeCode::SpectrumHoloByte,
+        0x49 => LicenseeCode::Irem,
+        0x4A => LicenseeCode::Virgin,
+        0x4D => LicenseeCode::MalibuInteractive,
+        0x4F => LicenseeCode::USGold,
+        0x50 => LicenseeCode::Absolute,
+        0x51 => LicenseeCode::Acclaim,
+        0x52 => LicenseeCode::Activision,
+        0x53 => LicenseeCode::SammyUSA,
+        0x54 => LicenseeCode::GameTek,
+        0x55 => LicenseeCode::ParkPlace,
+        0x56 => LicenseeCode::Ljn,
+        0x57 => LicenseeCode::Matchbox,
+        0x59 => LicenseeCode::MiltonBradley,
+        0x5A => LicenseeCode::Mindscape,
+        0x5B => LicenseeCode::Romstar,
+        0x5C => LicenseeCode::NaxatSoft,
+        0x5D => LicenseeCode::Tradewest,
+        0x60 => LicenseeCode::Titus,
+        0x61 => LicenseeCode::Virgin,
+        0x67 => LicenseeCode::Ocean,
+        0x69 => LicenseeCode::ElectronicArts,
+        0x6E => LicenseeCode::EliteSystems,
+        0x6F => LicenseeCode::ElectroBrain,
+        0x70 => LicenseeCode::Infogrames,
+        0x71 => LicenseeCode::Interplay,
+        0x72 => LicenseeCode::Broderbund,
+        0x73 => LicenseeCode::SculpturedSoftware,
+        0x75 => LicenseeCode::TheSalesCurve,
+        0x78 => LicenseeCode::Thq,
+        0x79 => LicenseeCode::Accolade,
+        0x7A => LicenseeCode::TriffixEntertainment,
+        0x7C => LicenseeCode::MicroProse,
+        0x7F => LicenseeCode::Kemco,
+        0x80 => LicenseeCode::MisawaEntertainment,
+        0x83 => LicenseeCode::Lozc,
+        0x86 => LicenseeCode::TokumaShoten,
+        0x8B => LicenseeCode::BulletProofSoftware,
+        0x8C => LicenseeCode::VicTokai,
+        0x8E => LicenseeCode::Ape,
+        0x8F => LicenseeCode::IMax,
+        0x91 => LicenseeCode::Chunsoft,
+        0x92 => LicenseeCode::VideoSystem,
+        0x93 => LicenseeCode::TsubarayaProductions,
+        0x95 => LicenseeCode::Varie,
+        0x96 => LicenseeCode::YonezawaSPal,
+        0x97 => LicenseeCode::Kemco,
+        0x99 => LicenseeCode::Arc,
+        0x9A => LicenseeCode::NihonBussan,
+        0x9B => LicenseeCode::Tecmo,
+        0x9C => LicenseeCode::Imagineer,
+        0x9D => LicenseeCode::Banpresto,
+        0x9F => LicenseeCode::Nova,
+        0xA1 => LicenseeCode::HoriElectric,
+        0xA2 => LicenseeCode::Bandai,
+        0xA4 => LicenseeCode::Konami,
+        0xA6 => LicenseeCode::Kawada,
+        0xA7 => LicenseeCode::Takara,
+        0xA9 => LicenseeCode::TechnosJapan,
+        0xAA => LicenseeCode::Broderbund,
+        0xAC => LicenseeCode::ToeiAnimation,
+        0xAD => LicenseeCode::Toho,
         0xAF => LicenseeCode::Namco,
-        _ => LicenseeCode::Unknown,
+        0xB0 => LicenseeCode::Acclaim,
+        0xB1 => LicenseeCode::ASCIIOrNexsoft,
+        0xB2 => LicenseeCode::Bandai,
+        0xB4 => LicenseeCode::SquareEnix,
+        0xB6 => LicenseeCode::HALLaboratory,
+        0xB7 => LicenseeCode::Snk,
+        0xB9 => LicenseeCode::PonyCanyon,
+        0xBA => LicenseeCode::CultureBrain,
+        0xBB => LicenseeCode::Sunsoft,
+        0xBD => LicenseeCode::SonyImagesoft,
+        0xBF => LicenseeCode::SammyCorporation,
+        0xC0 => LicenseeCode::Taito,
+        0xC2 => LicenseeCode::Kemco,
+        0xC3 => LicenseeCode::Square,
+        0xC4 => LicenseeCode::TokumaShoten,
+        0xC5 => LicenseeCode::DataEast,
+        0xC6 => LicenseeCode::TonkinHouse,
+        0xC8 => LicenseeCode::Koei,
+        0xC9 => LicenseeCode::Ufl,
+        0xCA => LicenseeCode::UltraGames,
+        0xCB => LicenseeCode::Vap,
+        0xCC => LicenseeCode::UseCorporation,
+        0xCD => LicenseeCode::Meldac,
+        0xCE => LicenseeCode::PonyCanyon,
+        0xCF => LicenseeCode::Angel,
+        0xD0 => LicenseeCode::Taito,
+        0xD1 => LicenseeCode::Sofel,
+        0xD2 => LicenseeCode::Quest,
+        0xD3 => LicenseeCode::SigmaEnterprises,
+        0xD4 => LicenseeCode::ASKKodansha,
+        0xD6 => LicenseeCode::NaxatSoft,
+        0xD7 => LicenseeCode::CopyaSystem,
+        0xD9 => LicenseeCode::Banpresto,
+        0xDA => LicenseeCode::Tomy,
+        0xDB => LicenseeCode::Ljn,
+        0xDD => LicenseeCode::NipponComputerSystems,
+        0xDE => LicenseeCode::HumanEntertainment,
+        0xDF => LicenseeCode::Altron,
+        0xE0 => LicenseeCode::Jaleco,
+        0xE1 => LicenseeCode::TowaChiki,
+        0xE2 => LicenseeCode::Yutaka,
+        0xE3 => LicenseeCode::Varie,
+        0xE5 => LicenseeCode::Epoch,
+        0xE7 => LicenseeCode::Athena,
+        0xE8 => LicenseeCode::AsmikAce,
+        0xE9 => LicenseeCode::Natsume,
+        0xEA => LicenseeCode::KingRecords,
+        0xEB => LicenseeCode::Atlus,
+        0xEC => LicenseeCode::EpicSonyRecords,
+        0xEE => LicenseeCode::Igs,
+        0xF0 => LicenseeCode::AWave,
+        0xF3 => LicenseeCode::ExtremeEntertainment,
+        0xFF => LicenseeCode::Ljn,
+        _ => LicenseeCode::Unknown {
+            old: Some(code),
+            new: None,
+        },
+    }
+}
+
+fn write_restart_calls(writer: &mut DataWriter, calls: &RestartCalls) {
+    writer.write_bytes(&calls.rst_0);
+    writer.write_bytes(&calls.rst_1);
+    writer.write_bytes(&calls.rst_2);
+    writer.write_bytes(&calls.rst_3);
+    writer.write_bytes(&calls.rst_4);
+    writer.write_bytes(&calls.rst_5);
+    writer.write_bytes(&calls.rst_6);
+    writer.write_bytes(&calls.rst_7);
+}
+
+fn write_interrupts(writer: &mut DataWriter, interrupts: &Interrupts) {
+    writer.write_bytes(&interrupts.v_blank);
+    writer.write_bytes(&interrupts.lcd_stat);
+    writer.write_bytes(&interrupts.timer);
+    writer.write_bytes(&interrupts.serial);
+    writer.write_bytes(&interrupts.joypad);
+    writer.skip(0x98);
+}
+
+fn write_header(writer: &mut DataWriter, header: &Header) {
+    writer.write_bytes(&header.entry_point);
+    if header.logo_data.len() == NINTENDO_LOGO.len() {
+        writer.write_bytes(&header.logo_data);
+    } else {
+        writer.write_bytes(&NINTENDO_LOGO);
+    }
+
+    // the raw title area keeps bytes the fields lose (padding after the
+    // title, non ASCII bytes, unknown CGB flags), it is only re-encoded if
+    // the fields were changed
+    if raw_title_matches(header) {
+        writer.write_bytes(&header.raw_title);
+    } else {
+        match header.gbc_flag {
+            GBCFlag::GBOnly => writer.write_utf8_string(&header.game_title, 16),
+            GBCFlag::GBCAndGB | GBCFlag::GBCOnly => {
+                writer.write_utf8_string(&header.game_title, 11);
+                writer.write_utf8_string(&header.manufacturer_code, 4);
+                writer.write_u8(encode_gbc_flag(header.gbc_flag));
+            }
+        }
+    }
+
+    // same for the licensee bytes, several byte pairs map to the same
+    // licensee code
+    let (old_licensee_code, new_licensee_code) = if raw_licensee_code_matches(header) {
+        (header.raw_old_licensee_code, header.raw_new_licensee_code)
+    } else {
+        encode_licensee_code(header)
+    };
+    writer.write_bytes(&new_licensee_code);
+    writer.write_u8(encode_sgb_flag(header.sgb_flag));
+    writer.write_u8(header.cartridge_type.code());
+    writer.write_u8(header.rom_size.code());
+    writer.write_u8(header.ram_size.code());
+    writer.write_u8(encode_destination_code(header.destination_code));
+    writer.write_u8(old_licensee_code);
+    writer.write_u8(header.rom_version);
+    writer.write_u8(header.checksum);
+    writer.write_u16_be(header.global_checksum);
+}
+
+// Whether parsing the raw licensee bytes gives back the licensee code of the
+// header.
+fn raw_licensee_code_matches(header: &Header) -> bool {
+    let parsed = if header.raw_old_licensee_code == NEW_LICENCSEE_CODE_VAL {
+        parse_new_licensee_code(&header.raw_new_licensee_code)
+    } else {
+        parse_old_licensee_code(header.raw_old_licensee_code)
+    };
+    parsed == header.licensee_code
+}
+
+// Whether parsing `raw_title` gives back the title, manufacturer code and
+// CGB flag of the header.
+fn raw_title_matches(header: &Header) -> bool {
+    let raw = &header.raw_title;
+    let mut reader = DataReader::new(raw);
+    let (title, manufacturer_code) = match parse_gbc_flag(raw[15], false) {
+        Ok(flag) if flag != header.gbc_flag => return false,
+        Ok(GBCFlag::GBOnly) => (reader.read_utf8_string_trimmed(16), String::new()),
+        Ok(_) => (
+            reader.read_utf8_string_trimmed(11),
+            reader.read_utf8_string_trimmed(4),
+        ),
+        Err(_) => return false,
+    };
+    title == header.game_title && manufacturer_code == header.manufacturer_code
+}
+
+fn encode_gbc_flag(flag: GBCFlag) -> u8 {
+    match flag {
+        GBCFlag::GBOnly => 0,
+        GBCFlag::GBCAndGB => 0x80,
+        GBCFlag::GBCOnly => 0xC0,
+    }
+}
+
+fn encode_sgb_flag(flag: SGBFlag) -> u8 {
+    match flag {
+        SGBFlag::NoSGB => 0x00,
+        SGBFlag::SGBSupport => 0x03,
+        SGBFlag::Unknown(v) => v,
+    }
+}
+
+fn encode_destination_code(v: DestinationCode) -> u8 {
+    match v {
+        DestinationCode::Japanese => 0x00,
+        DestinationCode::NonJapanese => 0x01,
+        DestinationCode::Unknown(v) => v,
+    }
+}
+
+// Returns the old licensee byte and the two new licensee bytes. The header
+// does not record which of the codes was used, so the new code is chosen if
+// the header looks like it belongs to a cartridge that requires it (SGB and
+// CGB support both require the old code to be 0x33) or if the licensee only
+// exists in the new table.
+fn encode_licensee_code(header: &Header) -> (u8, [u8; 2]) {
+    let no_new_code = [0, 0];
+    match header.licensee_code {
+        LicenseeCode::Unknown { old: Some(old), .. } => (old, no_new_code),
+        LicenseeCode::Unknown { new: Some(new), .. } => (NEW_LICENCSEE_CODE_VAL, new),
+        LicenseeCode::Unknown { .. } => (0x00, no_new_code),
+        ref code => {
+            let prefer_new = !header.manufacturer_code.is_empty()
+                || !matches!(header.gbc_flag, GBCFlag::GBOnly)
+                || matches!(header.sgb_flag, SGBFlag::SGBSupport);
+            match (old_licensee_code_value(code), new_licensee_code_value(code)) {
+                (Some(old), None) => (old, no_new_code),
+                (Some(old), Some(_)) if !prefer_new => (old, no_new_code),
+                (_, Some(new)) => (NEW_LICENCSEE_CODE_VAL, new),
+                (None, None) => (0x00, no_new_code),
+            }
+        }
+    }
+}
+
+/// Licensees that only ever appear in the old licensee code never
+/// published for the Game Boy Color.
+fn is_pre_cgb_licensee(code: &LicenseeCode) -> bool {
+    match code {
+        LicenseeCode::Unknown { old, .. } => old.is_some(),
+        code => old_licensee_code_value(code).is_some() && new_licensee_code_value(code).is_none(),
+    }
+}
+
+fn new_licensee_code_value(code: &LicenseeCode) -> Option<[u8; 2]> {
+    match code {
+        LicenseeCode::None => Some(*b"00"),
+        LicenseeCode::Nintendo => Some(*b"01"),
+        LicenseeCode::Capcom => Some(*b"08"),
+        LicenseeCode::ElectronicArts => Some(*b"13"),
+        LicenseeCode::HudsonSoft => Some(*b"18"),
+        LicenseeCode::BAi => Some(*b"19"),
+        LicenseeCode::Kss => Some(*b"20"),
+        LicenseeCode::PlanningOfficeWada => Some(*b"22"),
+        LicenseeCode::PCMComplete => Some(*b"24"),
+        LicenseeCode::SanX => Some(*b"25"),
+        LicenseeCode::Kemco => Some(*b"28"),
+        LicenseeCode::Seta => Some(*b"29"),
+        LicenseeCode::Viacom => Some(*b"30"),
+        LicenseeCode::Bandai => Some(*b"32"),
+        LicenseeCode::OceanAcclaim => Some(*b"33"),
+        LicenseeCode::Konami => Some(*b"34"),
+        LicenseeCode::HectorSoft => Some(*b"35"),
+        LicenseeCode::Taito => Some(*b"37"),
+        LicenseeCode::Banpresto => Some(*b"39"),
+        LicenseeCode::UbiSoft => Some(*b"41"),
+        LicenseeCode::Atlus => Some(*b"42"),
+        LicenseeCode::MalibuInteractive => Some(*b"44"),
+        LicenseeCode::Angel => Some(*b"46"),
+        LicenseeCode::BulletProofSoftware => Some(*b"47"),
+        LicenseeCode::Irem => Some(*b"49"),
+        LicenseeCode::Absolute => Some(*b"50"),
+        LicenseeCode::Acclaim => Some(*b"51"),
+        LicenseeCode::Activision => Some(*b"52"),
+        LicenseeCode::SammyUSA => Some(*b"53"),
+        LicenseeCode::HiTechExpressions => Some(*b"55"),
+        LicenseeCode::Ljn => Some(*b"56"),
+        LicenseeCode::Matchbox => Some(*b"57"),
+        LicenseeCode::Mattel => Some(*b"58"),
+        LicenseeCode::MiltonBradley => Some(*b"59"),
+        LicenseeCode::Titus => Some(*b"60"),
+        LicenseeCode::Virgin => Some(*b"61"),
+        LicenseeCode::LucasfilmGames => Some(*b"64"),
+        LicenseeCode::Ocean => Some(*b"67"),
+        LicenseeCode::Infogrames => Some(*b"70"),
+        LicenseeCode::Interplay => Some(*b"71"),
+        LicenseeCode::Broderbund => Some(*b"72"),
+        LicenseeCode::SculpturedSoftware => Some(*b"73"),
+        LicenseeCode::TheSalesCurve => Some(*b"75"),
+        LicenseeCode::Thq => Some(*b"78"),
+        LicenseeCode::Accolade => Some(*b"79"),
+        LicenseeCode::MisawaEntertainment => Some(*b"80"),
+        LicenseeCode::Lozc => Some(*b"83"),
+        LicenseeCode::TokumaShoten => Some(*b"86"),
+        LicenseeCode::TsukudaOriginal => Some(*b"87"),
+        LicenseeCode::Chunsoft => Some(*b"91"),
+        LicenseeCode::VideoSystem => Some(*b"92"),
+        LicenseeCode::Varie => Some(*b"95"),
+        LicenseeCode::YonezawaSPal => Some(*b"96"),
+        LicenseeCode::Kaneko => Some(*b"97"),
+        LicenseeCode::PackInVideo => Some(*b"99"),
+        LicenseeCode::BottomUp => Some(*b"9H"),
+        LicenseeCode::KonamiYuGiOh => Some(*b"A4"),
+        LicenseeCode::Mto => Some(*b"BL"),
+        LicenseeCode::Kodansha => Some(*b"DK"),
+        _ => None,
+    }
+}
+
+fn old_licensee_code_value(code: &LicenseeCode) -> Option<u8> {
+    match code {
+        LicenseeCode::None => Some(0x00),
+        LicenseeCode::Nintendo => Some(0x01),
+        LicenseeCode::Capcom => Some(0x08),
+        LicenseeCode::HotB => Some(0x09),
+        LicenseeCode::Jaleco => Some(0x0A),
+        LicenseeCode::CoconutsJapan => Some(0x0B),
+        LicenseeCode::EliteSystems => Some(0x0C),
+        LicenseeCode::ElectronicArts => Some(0x13),
+        LicenseeCode::HudsonSoft => Some(0x18),
+        LicenseeCode::ITCEntertainment => Some(0x19),
+        LicenseeCode::Yanoman => Some(0x1A),
+        LicenseeCode::JapanClary => Some(0x1D),
+        LicenseeCode::Virgin => Some(0x1F),
+        LicenseeCode::PCMComplete => Some(0x24),
+        LicenseeCode::SanX => Some(0x25),
+        LicenseeCode::Kemco => Some(0x28),
+        LicenseeCode::Seta => Some(0x29),
+        LicenseeCode::Infogrames => Some(0x30),
+        LicenseeCode::Bandai => Some(0x32),
+        LicenseeCode::Konami => Some(0x34),
+        LicenseeCode::HectorSoft => Some(0x35),
+        LicenseeCode::Banpresto => Some(0x39),
+        LicenseeCode::EntertainmentInteractive => Some(0x3C),
+        LicenseeCode::Gremlin => Some(0x3E),
+        LicenseeCode::UbiSoft => Some(0x41),
+        LicenseeCode::Atlus => Some(0x42),
+        LicenseeCode::MalibuInteractive => Some(0x44),
+        LicenseeCode::Angel => Some(0x46),
+        LicenseeCode::SpectrumHoloByte => Some(0x47),
+        LicenseeCode::Irem => Some(0x49),
+        LicenseeCode::USGold => Some(0x4F),
+        LicenseeCode::Absolute => Some(0x50),
+        LicenseeCode::Acclaim => Some(0x51),
+        LicenseeCode::Activision => Some(0x52),
+        LicenseeCode::SammyUSA => Some(0x53),
+        LicenseeCode::GameTek => Some(0x54),
+        LicenseeCode::ParkPlace => Some(0x55),
+        LicenseeCode::Ljn => Some(0x56),
+        LicenseeCode::Matchbox => Some(0x57),
+        LicenseeCode::MiltonBradley => Some(0x59),
+        LicenseeCode::Mindscape => Some(0x5A),
+        LicenseeCode::Romstar => Some(0x5B),
+        LicenseeCode::NaxatSoft => Some(0x5C),
+        LicenseeCode::Tradewest => Some(0x5D),
+        LicenseeCode::Titus => Some(0x60),
+        LicenseeCode::Ocean => Some(0x67),
+        LicenseeCode::ElectroBrain => Some(0x6F),
+        LicenseeCode::Interplay => Some(0x71),
+        LicenseeCode::Broderbund => Some(0x72),
+        LicenseeCode::SculpturedSoftware => Some(0x73),
+        LicenseeCode::TheSalesCurve => Some(0x75),
+        LicenseeCode::Thq => Some(0x78),
+        LicenseeCode::Accolade => Some(0x79),
+        LicenseeCode::TriffixEntertainment => Some(0x7A),
+        LicenseeCode::MicroProse => Some(0x7C),
+        LicenseeCode::MisawaEntertainment => Some(0x80),
+        LicenseeCode::Lozc => Some(0x83),
+        LicenseeCode::TokumaShoten => Some(0x86),
+        LicenseeCode::BulletProofSoftware => Some(0x8B),
+        LicenseeCode::VicTokai => Some(0x8C),
+        LicenseeCode::Ape => Some(0x8E),
+        LicenseeCode::IMax => Some(0x8F),
+        LicenseeCode::Chunsoft => Some(0x91),
+        LicenseeCode::VideoSystem => Some(0x92),
+        LicenseeCode::TsubarayaProductions => Some(0x93),
+        LicenseeCode::Varie => Some(0x95),
+        LicenseeCode::YonezawaSPal => Some(0x96),
+        LicenseeCode::Arc => Some(0x99),
+        LicenseeCode::NihonBussan => Some(0x9A),
+        LicenseeCode::Tecmo => Some(0x9B),
+        LicenseeCode::Imagineer => Some(0x9C),
+        LicenseeCode::Nova => Some(0x9F),
+        LicenseeCode::HoriElectric => Some(0xA1),
+        LicenseeCode::Kawada => Some(0xA6),
+        LicenseeCode::Takara => Some(0xA7),
+        LicenseeCode::TechnosJapan => Some(0xA9),
+        LicenseeCode::ToeiAnimation => Some(0xAC),
+        LicenseeCode::Toho => Some(0xAD),
+        LicenseeCode::Namco => Some(0xAF),
+        LicenseeCode::ASCIIOrNexsoft => Some(0xB1),
+        LicenseeCode::SquareEnix => Some(0xB4),
+        LicenseeCode::HALLaboratory => Some(0xB6),
+        LicenseeCode::Snk => Some(0xB7),
+        LicenseeCode::PonyCanyon => Some(0xB9),
+        LicenseeCode::CultureBrain => Some(0xBA),
+        LicenseeCode::Sunsoft => Some(0xBB),
+        LicenseeCode::SonyImagesoft => Some(0xBD),
+        LicenseeCode::SammyCorporation => Some(0xBF),
+        LicenseeCode::Taito => Some(0xC0),
+        LicenseeCode::Square => Some(0xC3),
+        LicenseeCode::DataEast => Some(0xC5),
+        LicenseeCode::TonkinHouse => Some(0xC6),
+        LicenseeCode::Koei => Some(0xC8),
+        LicenseeCode::Ufl => Some(0xC9),
+        LicenseeCode::UltraGames => Some(0xCA),
+        LicenseeCode::Vap => Some(0xCB),
+        LicenseeCode::UseCorporation => Some(0xCC),
+        LicenseeCode::Meldac => Some(0xCD),
+        LicenseeCode::Sofel => Some(0xD1),
+        LicenseeCode::Quest => Some(0xD2),
+        LicenseeCode::SigmaEnterprises => Some(0xD3),
+        LicenseeCode::ASKKodansha => Some(0xD4),
+        LicenseeCode::CopyaSystem => Some(0xD7),
+        LicenseeCode::Tomy => Some(0xDA),
+        LicenseeCode::NipponComputerSystems => Some(0xDD),
+        LicenseeCode::HumanEntertainment => Some(0xDE),
+        LicenseeCode::Altron => Some(0xDF),
+        LicenseeCode::TowaChiki => Some(0xE1),
+        LicenseeCode::Yutaka => Some(0xE2),
+        LicenseeCode::Epoch => Some(0xE5),
+        LicenseeCode::Athena => Some(0xE7),
+        LicenseeCode::AsmikAce => Some(0xE8),
+        LicenseeCode::Natsume => Some(0xE9),
+        LicenseeCode::KingRecords => Some(0xEA),
+        LicenseeCode::EpicSonyRecords => Some(0xEC),
+        LicenseeCode::Igs => Some(0xEE),
+        LicenseeCode::AWave => Some(0xF0),
+        LicenseeCode::ExtremeEntertainment => Some(0xF3),
+        _ => None,
     }
-    // TODO complete this mapping
 }
 
 fn clean_string(str: &str) -> String {
     str.replace('\0', "")
 }
 
-fn parse_bank_data(reader: &mut DataReader, rom_size: ROMSize) -> Result<Vec<Vec<u8>>, String> {
-    let n = num_banks(rom_size);
+// Banks are read from the start of the ROM, bank 0 includes the vectors and
+// the header that were parsed before.
+fn parse_bank_data(data: &[u8], n: usize) -> Result<Vec<Vec<u8>>, GBError> {
+    let expected_bytes = BANK_BYTES * n;
 
-    let expected_bytes = (BANK_BYTES * n) - DATA_START;
-
-    if reader.unread_bytes().len() < expected_bytes {
-        return Err(format!(
-            "illegal ROM, not enough bank data. Expected {} bytes, got {} bytes in ROM",
-            expected_bytes,
-            reader.unread_bytes().len()
-        ));
+    if data.len() < expected_bytes {
+        return Err(GBError::NotEnoughBankData {
+            expected: expected_bytes,
+            actual: data.len(),
+        });
     }
 
+    let mut reader = DataReader::new(data);
     let mut bank_data = Vec::with_capacity(n);
-    for b in 0..n {
-        let bank_size = if b == (n - 1) {
-            BANK_BYTES - DATA_START
-        } else {
-            BANK_BYTES
-        };
-        let mut bank = Vec::with_capacity(bank_size);
-        for _ in 0..bank_size {
-            bank.push(reader.read_u8());
-        }
-        bank_data.push(bank);
+    for _ in 0..n {
+        bank_data.push(reader.bytes().take(BANK_BYTES).collect());
     }
     Ok(bank_data)
 }
@@ -0,0 +1,103 @@
+// There is no mmap crate to depend on, so the libc functions are declared
+// here. The constants and types below are only checked against 64-bit Linux
+// and macOS, the module is not built anywhere else.
+use crate::{GBBinary, GBError, load};
+use std::ffi::{c_int, c_void};
+use std::fs::File;
+use std::io;
+use std::os::fd::AsRawFd;
+use std::path::Path;
+
+// `off_t` is 64 bits wide on every supported target
+#[allow(non_camel_case_types)]
+type off_t = i64;
+
+// <sys/mman.h> of glibc/musl and of the macOS SDK
+#[cfg(target_os = "linux")]
+const PROT_READ: c_int = 0x1;
+#[cfg(target_os = "linux")]
+const MAP_PRIVATE: c_int = 0x02;
+#[cfg(target_os = "macos")]
+const PROT_READ: c_int = 0x01;
+#[cfg(target_os = "macos")]
+const MAP_PRIVATE: c_int = 0x0002;
+const MAP_FAILED: *mut c_void = !0 as *mut c_void;
+
+unsafe extern "C" {
+    fn mmap(
+        addr: *mut c_void,
+        len: usize,
+        prot: c_int,
+        flags: c_int,
+        fd: c_int,
+        offset: off_t,
+    ) -> *mut c_void;
+    fn munmap(addr: *mut c_void, len: usize) -> c_int;
+}
+
+// A read-only private mapping of a whole file, unmapped on drop.
+struct Mapping {
+    ptr: *mut c_void,
+    len: usize,
+}
+
+impl Mapping {
+    fn new(file: &File) -> io::Result<Mapping> {
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "file too large to map"))?;
+        if len == 0 {
+            // mmap rejects empty mappings
+            return Ok(Mapping {
+                ptr: std::ptr::null_mut(),
+                len: 0,
+            });
+        }
+        let ptr = unsafe {
+            mmap(
+                std::ptr::null_mut(),
+                len,
+                PROT_READ,
+                MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Mapping { ptr, len })
+    }
+
+    fn data(&self) -> &[u8] {
+        if self.len == 0 {
+            return &[];
+        }
+        // the mapping stays valid and readable until it is dropped
+        unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        if self.len != 0 {
+            unsafe {
+                munmap(self.ptr, self.len);
+            }
+        }
+    }
+}
+
+/// Loads a ROM by memory-mapping the file instead of reading it into the
+/// heap first. The parser runs directly over the mapped pages.
+///
+/// The file must not be modified while it is being parsed. If it is
+/// truncated while mapped, reading the pages past the new end raises
+/// `SIGBUS` and kills the process, use `load_from_reader` for files other
+/// processes may write to.
+///
+/// Only available on 64-bit Linux and macOS.
+pub fn load_mmap<P: AsRef<Path>>(path: P) -> Result<GBBinary, GBError> {
+    let file = File::open(path)?;
+    let mapping = Mapping::new(&file)?;
+    load(mapping.data())
+}
@@ -1,14 +1,18 @@
+use crate::error::GBError;
+use alloc::string::{String, ToString};
+
+#[derive(Clone)]
 pub struct DataReader<'a> {
     data: &'a [u8],
     offset: usize,
 }
 
-impl DataReader<'_> {
-    pub fn new(data: &[u8]) -> DataReader<'_> {
+impl<'a> DataReader<'a> {
+    pub fn new(data: &'a [u8]) -> DataReader<'a> {
         DataReader::new_with_offset(data, 0)
     }
 
-    pub fn new_with_offset(data: &[u8], offset: usize) -> DataReader<'_> {
+    pub fn new_with_offset(data: &'a [u8], offset: usize) -> DataReader<'a> {
         DataReader { data, offset }
     }
 
@@ -19,6 +23,15 @@ impl DataReader<'_> {
         str
     }
 
+    // reads a C-string style field: the string ends at the first 0x00, but
+    // the offset always advances by max_len
+    pub fn read_utf8_string_trimmed(&mut self, max_len: usize) -> String {
+        let field = &self.data[self.offset..(self.offset + max_len)];
+        let len = field.iter().position(|b| *b == 0).unwrap_or(max_len);
+        self.offset += max_len;
+        String::from_utf8_lossy(&field[..len]).to_string()
+    }
+
     pub fn read_u64(&mut self) -> u64 {
         let u = u64::from_le_bytes(
             self.data[self.offset..(self.offset + 8)]
@@ -49,6 +62,17 @@ impl DataReader<'_> {
         i
     }
 
+    // big-endian variant of read_u32
+    pub fn read_u32_be(&mut self) -> u32 {
+        let u = u32::from_be_bytes(
+            self.data[self.offset..(self.offset + 4)]
+                .try_into()
+                .unwrap(),
+        );
+        self.offset += 4;
+        u
+    }
+
     pub fn read_u16(&mut self) -> u16 {
         let u = u16::from_le_bytes(
             self.data[self.offset..(self.offset + 2)]
@@ -59,6 +83,17 @@ impl DataReader<'_> {
         u
     }
 
+    // big-endian variant of read_u16, e.g. for the global checksum
+    pub fn read_u16_be(&mut self) -> u16 {
+        let u = u16::from_be_bytes(
+            self.data[self.offset..(self.offset + 2)]
+                .try_into()
+                .unwrap(),
+        );
+        self.offset += 2;
+        u
+    }
+
     pub fn read_i16(&mut self) -> i16 {
         let i = i16::from_le_bytes(
             self.data[self.offset..(self.offset + 2)]
@@ -69,27 +104,148 @@ impl DataReader<'_> {
         i
     }
 
+    // returns the next n bytes as slice and advances the offset by n
+    pub fn read_bytes(&mut self, n: usize) -> &'a [u8] {
+        let bytes = &self.data[self.offset..(self.offset + n)];
+        self.offset += n;
+        bytes
+    }
+
+    pub fn try_read_bytes(&mut self, n: usize) -> Result<&'a [u8], GBError> {
+        self.check_available(n)?;
+        Ok(self.read_bytes(n))
+    }
+
     pub fn read_u8(&mut self) -> u8 {
         let u = self.data[self.offset];
         self.offset += 1;
         u
     }
 
+    // Peeks at the byte at the current offset without advancing it.
+    pub fn peek_u8(&self) -> u8 {
+        self.data[self.offset]
+    }
+
+    pub fn peek_u16(&self) -> u16 {
+        u16::from_le_bytes(
+            self.data[self.offset..(self.offset + 2)]
+                .try_into()
+                .unwrap(),
+        )
+    }
+
+    pub fn peek_bytes(&self, n: usize) -> &'a [u8] {
+        &self.data[self.offset..(self.offset + n)]
+    }
+
+    pub fn try_peek_u8(&self) -> Option<u8> {
+        self.check_available(1).ok()?;
+        Some(self.peek_u8())
+    }
+
+    pub fn try_peek_u16(&self) -> Option<u16> {
+        self.check_available(2).ok()?;
+        Some(self.peek_u16())
+    }
+
+    pub fn try_peek_bytes(&self, n: usize) -> Option<&'a [u8]> {
+        self.check_available(n).ok()?;
+        Some(self.peek_bytes(n))
+    }
+
     // Reads a byte without updating the current offset.
     pub fn read_u8_at(&self, offset: usize) -> u8 {
         self.data[offset]
     }
 
+    // reads the byte at an absolute offset, None if it is out of bounds
+    pub fn try_read_u8_at(&self, offset: usize) -> Option<u8> {
+        self.data.get(offset).copied()
+    }
+
+    pub fn try_read_utf8_string(&mut self, size: usize) -> Result<String, GBError> {
+        self.check_available(size)?;
+        Ok(self.read_utf8_string(size))
+    }
+
+    pub fn try_read_utf8_string_trimmed(&mut self, max_len: usize) -> Result<String, GBError> {
+        self.check_available(max_len)?;
+        Ok(self.read_utf8_string_trimmed(max_len))
+    }
+
+    pub fn try_read_u64(&mut self) -> Result<u64, GBError> {
+        self.check_available(8)?;
+        Ok(self.read_u64())
+    }
+
+    pub fn try_read_u32(&mut self) -> Result<u32, GBError> {
+        self.check_available(4)?;
+        Ok(self.read_u32())
+    }
+
+    pub fn try_read_u32_be(&mut self) -> Result<u32, GBError> {
+        self.check_available(4)?;
+        Ok(self.read_u32_be())
+    }
+
+    pub fn try_read_i32(&mut self) -> Result<i32, GBError> {
+        self.check_available(4)?;
+        Ok(self.read_i32())
+    }
+
+    pub fn try_read_u16(&mut self) -> Result<u16, GBError> {
+        self.check_available(2)?;
+        Ok(self.read_u16())
+    }
+
+    pub fn try_read_u16_be(&mut self) -> Result<u16, GBError> {
+        self.check_available(2)?;
+        Ok(self.read_u16_be())
+    }
+
+    pub fn try_read_i16(&mut self) -> Result<i16, GBError> {
+        self.check_available(2)?;
+        Ok(self.read_i16())
+    }
+
+    pub fn try_read_u8(&mut self) -> Result<u8, GBError> {
+        self.check_available(1)?;
+        Ok(self.read_u8())
+    }
+
+    // Fails with UnexpectedEof if less than `size` bytes are left to read.
+    fn check_available(&self, size: usize) -> Result<(), GBError> {
+        match self.offset.checked_add(size) {
+            Some(end) if end <= self.data.len() => Ok(()),
+            _ => Err(GBError::UnexpectedEof {
+                offset: self.offset,
+                needed: size,
+            }),
+        }
+    }
+
     pub fn read_bool(&mut self) -> bool {
-        let u = self.read_u16();
+        let u = self.read_u8();
         u != 0
     }
 
+    // yields the remaining bytes one by one, advancing the offset with
+    // every byte taken from the iterator
+    pub fn bytes(&mut self) -> impl Iterator<Item = u8> + '_ {
+        core::iter::from_fn(move || self.try_read_u8().ok())
+    }
+
     // returns a slice over the bytes that were not read so far
     pub fn unread_bytes(&self) -> &[u8] {
         &self.data[self.offset..]
     }
 
+    // returns the complete underlying data, independent of the current offset
+    pub fn data(&self) -> &[u8] {
+        self.data
+    }
+
     pub fn slice(&self, start: usize, end: usize) -> &[u8] {
         &self.data[start..end]
     }
@@ -98,7 +254,22 @@ impl DataReader<'_> {
         self.offset += bytes;
     }
 
+    // sets the offset to an absolute position
+    pub fn seek(&mut self, pos: usize) {
+        self.offset = pos;
+    }
+
+    // moves the offset back by n bytes
+    pub fn rewind(&mut self, n: usize) {
+        self.offset -= n;
+    }
+
+    // returns the number of bytes that were not read so far
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.offset)
+    }
+
     pub fn offset(&self) -> usize {
-        return self.offset;
+        self.offset
     }
 }
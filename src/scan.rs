@@ -0,0 +1,78 @@
+use crate::{GBError, Header, load_header};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The outcome of parsing one ROM file found by `scan_dir`, or the I/O
+/// error of a directory it could not read.
+pub struct ScanEntry {
+    pub path: PathBuf,
+    pub header: Result<Header, GBError>,
+}
+
+/// Walks `dir` recursively and parses the header of every `.gb` and `.gbc`
+/// file. A file that fails to parse is returned with its error, as is a
+/// subdirectory that can not be read, only an unreadable `dir` itself
+/// aborts the scan. Symlinks to directories are not followed, so a link
+/// loop can not recurse forever. Entries are sorted by path.
+pub fn scan_dir(dir: &Path) -> io::Result<Vec<ScanEntry>> {
+    let mut paths = Vec::new();
+    let mut entries = Vec::new();
+    collect_rom_paths(dir, fs::read_dir(dir)?, &mut paths, &mut entries);
+
+    entries.extend(paths.into_iter().map(|path| {
+        let header = fs::read(&path)
+            .map_err(GBError::from)
+            .and_then(|data| load_header(&data));
+        ScanEntry { path, header }
+    }));
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(entries)
+}
+
+// Adds the ROM paths below `dir` to `paths`. Entries and subdirectories that
+// can not be read are added to `errors` with their I/O error instead.
+fn collect_rom_paths(
+    dir: &Path,
+    read_dir: fs::ReadDir,
+    paths: &mut Vec<PathBuf>,
+    errors: &mut Vec<ScanEntry>,
+) {
+    let mut subdirs = Vec::new();
+    for entry in read_dir {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                errors.push(io_error(dir.to_path_buf(), e));
+                continue;
+            }
+        };
+        let path = entry.path();
+        // `DirEntry::file_type` does not follow symlinks, unlike `Path::is_dir`
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => subdirs.push(path),
+            Ok(_) if is_rom_path(&path) => paths.push(path),
+            Ok(_) => {}
+            Err(e) => errors.push(io_error(path, e)),
+        }
+    }
+    for path in subdirs {
+        match fs::read_dir(&path) {
+            Ok(read_dir) => collect_rom_paths(&path, read_dir, paths, errors),
+            Err(e) => errors.push(io_error(path, e)),
+        }
+    }
+}
+
+fn io_error(path: PathBuf, e: io::Error) -> ScanEntry {
+    ScanEntry {
+        path,
+        header: Err(GBError::from(e)),
+    }
+}
+
+fn is_rom_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gb") || ext.eq_ignore_ascii_case("gbc"))
+}
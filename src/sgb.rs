@@ -0,0 +1,140 @@
+//! Best-effort extraction of Super Game Boy command packets from ROM data.
+//!
+//! SGB games send their border, palette and multiplayer setup as 16 byte
+//! command packets, and most of them keep those packets as tables in bank 0.
+//! The first byte of a packet is `command << 3 | number_of_packets`. Because
+//! this means scanning code and data alike, the extractor only accepts
+//! packets that are very unlikely to be something else:
+//!
+//! - runs of at least two `DATA_SND` packets at a 16 byte stride, as found in
+//!   the SGB initialisation sequence most games copy from the SDK
+//! - single packet commands that take at most one parameter byte, with a
+//!   valid parameter and the remaining 14 bytes zero
+//!
+//! Palette and attribute packets carry arbitrary data and are not detected.
+
+use alloc::vec::Vec;
+
+pub const PACKET_BYTES: usize = 16;
+
+/// The SGB command of a packet, see the Pan Docs for the meaning of each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SgbCommand {
+    Pal01,
+    Pal23,
+    Pal03,
+    Pal12,
+    AttrBlk,
+    AttrLin,
+    AttrDiv,
+    AttrChr,
+    Sound,
+    SouTrn,
+    PalSet,
+    PalTrn,
+    AtrcEn,
+    TestEn,
+    IconEn,
+    DataSnd,
+    DataTrn,
+    MltReq,
+    Jump,
+    ChrTrn,
+    PctTrn,
+    AttrTrn,
+    AttrSet,
+    MaskEn,
+    ObjTrn,
+}
+
+impl SgbCommand {
+    pub fn from_code(code: u8) -> Option<SgbCommand> {
+        use SgbCommand::*;
+        const COMMANDS: [SgbCommand; 25] = [
+            Pal01, Pal23, Pal03, Pal12, AttrBlk, AttrLin, AttrDiv, AttrChr, Sound, SouTrn, PalSet,
+            PalTrn, AtrcEn, TestEn, IconEn, DataSnd, DataTrn, MltReq, Jump, ChrTrn, PctTrn,
+            AttrTrn, AttrSet, MaskEn, ObjTrn,
+        ];
+        COMMANDS.get(code as usize).copied()
+    }
+
+    // the largest valid parameter of commands that take at most one byte
+    fn max_single_param(&self) -> Option<u8> {
+        match self {
+            SgbCommand::SouTrn
+            | SgbCommand::PalTrn
+            | SgbCommand::DataTrn
+            | SgbCommand::PctTrn
+            | SgbCommand::AttrTrn => Some(0),
+            SgbCommand::AtrcEn | SgbCommand::TestEn | SgbCommand::IconEn | SgbCommand::ChrTrn => {
+                Some(1)
+            }
+            SgbCommand::MltReq | SgbCommand::MaskEn => Some(3),
+            _ => None,
+        }
+    }
+}
+
+/// A command packet found in ROM data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SgbPacket {
+    /// Offset of the packet in the scanned data.
+    pub offset: usize,
+    pub command: SgbCommand,
+    /// The number of packets the command consists of, 1-7.
+    pub length: u8,
+    pub data: [u8; PACKET_BYTES],
+}
+
+/// Scans `bank0` for SGB command packets, see the module docs for the
+/// heuristic. Packets are returned in the order they appear.
+pub fn extract_sgb_packets(bank0: &[u8]) -> Vec<SgbPacket> {
+    let mut packets = Vec::new();
+    let mut offset = 0;
+    while offset + PACKET_BYTES <= bank0.len() {
+        let run = data_snd_run(bank0, offset);
+        if run >= 2 {
+            for i in 0..run {
+                packets.extend(packet_at(bank0, offset + i * PACKET_BYTES));
+            }
+            offset += run * PACKET_BYTES;
+        } else if let Some(packet) = single_packet_at(bank0, offset) {
+            packets.push(packet);
+            offset += PACKET_BYTES;
+        } else {
+            offset += 1;
+        }
+    }
+    packets
+}
+
+fn packet_at(data: &[u8], offset: usize) -> Option<SgbPacket> {
+    let bytes: [u8; PACKET_BYTES] = data.get(offset..offset + PACKET_BYTES)?.try_into().ok()?;
+    let length = bytes[0] & 0x07;
+    if length == 0 {
+        return None;
+    }
+    Some(SgbPacket {
+        offset,
+        command: SgbCommand::from_code(bytes[0] >> 3)?,
+        length,
+        data: bytes,
+    })
+}
+
+// the number of consecutive DATA_SND packets starting at offset
+fn data_snd_run(data: &[u8], offset: usize) -> usize {
+    (0..)
+        .map(|i| packet_at(data, offset + i * PACKET_BYTES))
+        .take_while(|p| matches!(p, Some(p) if p.command == SgbCommand::DataSnd && p.length == 1))
+        .count()
+}
+
+fn single_packet_at(data: &[u8], offset: usize) -> Option<SgbPacket> {
+    let packet = packet_at(data, offset)?;
+    let max_param = packet.command.max_single_param()?;
+    let valid = packet.length == 1
+        && packet.data[1] <= max_param
+        && packet.data[2..].iter().all(|b| *b == 0);
+    valid.then_some(packet)
+}
@@ -0,0 +1,56 @@
+use crate::reader::DataReader;
+use crate::{
+    CartridgeType, DestinationCode, GBCFlag, GBError, LicenseeCode, LoadOptions, RAMSize, ROMSize,
+    SGBFlag, check_header_len, check_rom_size, parse_header_with, parse_rom_size,
+};
+use alloc::string::String;
+
+/// A value reported by `parse_streaming`, in ROM order.
+#[derive(Clone)]
+pub enum ParseEvent {
+    EntryPoint([u8; 4]),
+    Logo {
+        valid: bool,
+    },
+    Title(String),
+    ManufacturerCode(String),
+    GbcFlag(GBCFlag),
+    LicenseeCode(LicenseeCode),
+    SgbFlag(SGBFlag),
+    CartridgeType(CartridgeType),
+    RomSize(ROMSize),
+    RamSize(RAMSize),
+    DestinationCode(DestinationCode),
+    RomVersion(u8),
+    HeaderChecksum {
+        stored: u8,
+        valid: bool,
+    },
+    GlobalChecksum {
+        stored: u16,
+        valid: bool,
+    },
+    /// Bank `n` starts at `n * BANK_BYTES` in the parsed data.
+    BankStart(usize),
+}
+
+/// Parses `data` like `load`, but reports the header fields to `f` as the
+/// parser reads them instead of building a `GBBinary`, followed by the
+/// start of every bank. No bank data is copied.
+///
+/// The ROM size is checked before the first event, so `f` is never called
+/// for a ROM that `load` would reject.
+pub fn parse_streaming(data: &[u8], f: &mut dyn FnMut(ParseEvent)) -> Result<(), GBError> {
+    check_header_len(data)?;
+    let opts = LoadOptions::default();
+    let mut reader = DataReader::new_with_offset(data, 0x148);
+    let rom_size = parse_rom_size(&mut reader, opts.strict)?;
+    check_rom_size(rom_size, data.len(), &opts)?;
+
+    reader.seek(0x100);
+    let header = parse_header_with(&mut reader, &opts, f)?;
+    for bank in 0..header.rom_size.num_banks() {
+        f(ParseEvent::BankStart(bank));
+    }
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use crate::reader::DataReader;
+use crate::{
+    BANK_BYTES, CartridgeType, DestinationCode, GBCFlag, GBError, Header, LicenseeCode,
+    NEW_LICENCSEE_CODE_VAL, NINTENDO_LOGO, RAMSize, ROMSize, SGBFlag, check_header_len,
+    computed_header_checksum, load_header, parse_cartridge_type, parse_destination_code,
+    parse_gbc_flag, parse_new_licensee_code, parse_old_licensee_code, parse_ram_size,
+    parse_rom_size, parse_sgb_flag,
+};
+
+/// A read-only view over a ROM image that borrows the original buffer.
+///
+/// Unlike `GBBinary` no bank data is copied, banks are handed out as slices
+/// into the buffer the view was created from.
+#[derive(Clone)]
+pub struct RomView<'a> {
+    data: &'a [u8],
+    header: Header,
+}
+
+impl<'a> RomView<'a> {
+    pub fn new(data: &'a [u8]) -> Result<RomView<'a>, GBError> {
+        let header = load_header(data)?;
+
+        let expected = header.rom_size.bytes();
+        if data.len() < expected {
+            return Err(GBError::NotEnoughBankData {
+                expected,
+                actual: data.len(),
+            });
+        }
+        Ok(RomView { data, header })
+    }
+
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    pub fn num_banks(&self) -> usize {
+        self.header.rom_size.num_banks()
+    }
+
+    /// Returns the bank with the given index or `None` if the index is
+    /// beyond the declared number of banks.
+    pub fn bank(&self, index: usize) -> Option<&'a [u8]> {
+        if index >= self.num_banks() {
+            return None;
+        }
+        let start = index * BANK_BYTES;
+        Some(&self.data[start..start + BANK_BYTES])
+    }
+
+    pub fn banks(&self) -> impl Iterator<Item = &'a [u8]> + use<'a> {
+        self.data.chunks_exact(BANK_BYTES).take(self.num_banks())
+    }
+}
+
+/// A borrowed view of the header of a ROM that never allocates, for
+/// targets without an allocator.
+///
+/// Fields are decoded from the borrowed bytes on every call, leniently as
+/// `load_header` does. The title and manufacturer code are returned as byte
+/// slices instead of strings.
+#[derive(Clone, Copy)]
+pub struct HeaderRef<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> HeaderRef<'a> {
+    pub fn new(data: &'a [u8]) -> Result<HeaderRef<'a>, GBError> {
+        check_header_len(data)?;
+        Ok(HeaderRef { data })
+    }
+
+    pub fn entry_point(&self) -> &'a [u8] {
+        &self.data[0x100..0x104]
+    }
+
+    pub fn logo_valid(&self) -> bool {
+        self.data[0x104..0x134] == NINTENDO_LOGO
+    }
+
+    /// The unmodified title area at 0x134-0x143.
+    pub fn raw_title(&self) -> &'a [u8] {
+        &self.data[0x134..0x144]
+    }
+
+    /// The title up to the first `\0`, 11 bytes at most for CGB cartridges.
+    pub fn title(&self) -> &'a [u8] {
+        match self.gbc_flag() {
+            GBCFlag::GBOnly => until_nul(&self.data[0x134..0x144]),
+            GBCFlag::GBCAndGB | GBCFlag::GBCOnly => until_nul(&self.data[0x134..0x13F]),
+        }
+    }
+
+    /// The manufacturer code up to the first `\0`, empty for cartridges
+    /// without CGB support.
+    pub fn manufacturer_code(&self) -> &'a [u8] {
+        match self.gbc_flag() {
+            GBCFlag::GBOnly => &[],
+            GBCFlag::GBCAndGB | GBCFlag::GBCOnly => until_nul(&self.data[0x13F..0x143]),
+        }
+    }
+
+    pub fn gbc_flag(&self) -> GBCFlag {
+        parse_gbc_flag(self.data[0x143], false).unwrap_or(GBCFlag::GBOnly)
+    }
+
+    pub fn licensee_code(&self) -> LicenseeCode {
+        let old = self.data[0x14B];
+        if old == NEW_LICENCSEE_CODE_VAL {
+            parse_new_licensee_code(&[self.data[0x144], self.data[0x145]])
+        } else {
+            parse_old_licensee_code(old)
+        }
+    }
+
+    pub fn sgb_flag(&self) -> SGBFlag {
+        self.decode(0x146, parse_sgb_flag)
+    }
+
+    pub fn cartridge_type(&self) -> CartridgeType {
+        self.decode(0x147, parse_cartridge_type)
+    }
+
+    pub fn rom_size(&self) -> ROMSize {
+        self.decode(0x148, parse_rom_size)
+    }
+
+    pub fn ram_size(&self) -> RAMSize {
+        self.decode(0x149, parse_ram_size)
+    }
+
+    pub fn destination_code(&self) -> DestinationCode {
+        self.decode(0x14A, parse_destination_code)
+    }
+
+    pub fn rom_version(&self) -> u8 {
+        self.data[0x14C]
+    }
+
+    pub fn checksum(&self) -> u8 {
+        self.data[0x14D]
+    }
+
+    pub fn checksum_valid(&self) -> bool {
+        self.checksum() == computed_header_checksum(self.data)
+    }
+
+    pub fn global_checksum(&self) -> u16 {
+        u16::from_be_bytes([self.data[0x14E], self.data[0x14F]])
+    }
+
+    // lenient parsing maps unknown bytes to `Unknown` and never fails, the
+    // byte itself is in bounds after `check_header_len`
+    fn decode<T>(
+        &self,
+        offset: usize,
+        parse: fn(&mut DataReader, bool) -> Result<T, GBError>,
+    ) -> T {
+        let mut reader = DataReader::new_with_offset(self.data, offset);
+        parse(&mut reader, false).expect("lenient parsing does not fail")
+    }
+}
+
+fn until_nul(bytes: &[u8]) -> &[u8] {
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    &bytes[..end]
+}
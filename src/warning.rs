@@ -0,0 +1,48 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// A suspicious but parseable header value, see `Header::validate_consistency`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-kebab-case", serde(rename_all = "kebab-case"))]
+pub enum Warning {
+    /// The cartridge type has no RAM, but a RAM size is declared.
+    RamDeclaredWithoutRam,
+    /// The cartridge type has RAM, but no RAM size is declared.
+    RamNotDeclared,
+    /// MBC2 has built-in RAM, the header should declare no external RAM.
+    Mbc2ExternalRam,
+    /// The ROM version is higher than `MAX_PLAUSIBLE_ROM_VERSION`.
+    ImplausibleRomVersion(u8),
+    /// The cartridge type has battery backed RAM, but no RAM size is declared.
+    BatteryWithoutRam,
+    /// The ROM is Game Boy Color only, but its Japanese destination and
+    /// pre-CGB licensee point to an older release.
+    CgbFlagMismatch,
+    /// The ROM size is one of the undocumented 72, 80 or 96 bank sizes
+    /// (0x52-0x54), holds the raw size byte.
+    UnusualRomSize(u8),
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::RamDeclaredWithoutRam => {
+                f.write_str("RAM size declared but cartridge type has no RAM")
+            }
+            Warning::RamNotDeclared => {
+                f.write_str("cartridge type has RAM but no RAM size declared")
+            }
+            Warning::Mbc2ExternalRam => f.write_str("MBC2 cartridge declares external RAM"),
+            Warning::BatteryWithoutRam => {
+                f.write_str("cartridge type has battery but no RAM declared")
+            }
+            Warning::CgbFlagMismatch => {
+                f.write_str("GBC only flag on a ROM from a pre-GBC licensee")
+            }
+            Warning::ImplausibleRomVersion(v) => write!(f, "implausibly high ROM version: {:x}", v),
+            Warning::UnusualRomSize(v) => write!(f, "unusual ROM size: {:x}", v),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Clone)]
+pub struct DataWriter {
+    data: Vec<u8>,
+    offset: usize,
+}
+
+impl DataWriter {
+    pub fn new(size: usize) -> DataWriter {
+        DataWriter::new_with_data(vec![0; size])
+    }
+
+    pub fn new_with_data(data: Vec<u8>) -> DataWriter {
+        DataWriter { data, offset: 0 }
+    }
+
+    // Writes the string bytes and pads with 0 up to size. Longer strings
+    // are truncated to size.
+    pub fn write_utf8_string(&mut self, str: &str, size: usize) {
+        let bytes = str.as_bytes();
+        let len = bytes.len().min(size);
+        self.data[self.offset..(self.offset + len)].copy_from_slice(&bytes[..len]);
+        self.data[(self.offset + len)..(self.offset + size)].fill(0);
+        self.offset += size;
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.data[self.offset..(self.offset + bytes.len())].copy_from_slice(bytes);
+        self.offset += bytes.len();
+    }
+
+    pub fn write_u16(&mut self, u: u16) {
+        self.write_bytes(&u.to_le_bytes());
+    }
+
+    pub fn write_u16_be(&mut self, u: u16) {
+        self.write_bytes(&u.to_be_bytes());
+    }
+
+    pub fn write_u8(&mut self, u: u8) {
+        self.data[self.offset] = u;
+        self.offset += 1;
+    }
+
+    pub fn skip(&mut self, bytes: usize) {
+        self.offset += bytes;
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    // returns the written data
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
@@ -0,0 +1,220 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::hashes::crc32;
+use mule_gb::{
+    BANK_BYTES, GBError, LoadOptions, NINTENDO_LOGO, RomView, load, load_bank, load_with_options,
+};
+
+#[test]
+fn test_bank_alignment() {
+    let mut rom = make_rom(&RomSpec {
+        cartridge_type: 0x01,
+        rom_size: 0x01,
+        ..Default::default()
+    });
+    rom[BANK_BYTES] = 0xAB;
+    let gb = load(&rom).expect("load");
+
+    assert_eq!(gb.bank_data.len(), 4);
+    assert!(gb.bank_data.iter().all(|b| b.len() == BANK_BYTES));
+    assert_eq!(gb.bank_data[0][0x100], gb.header.entry_point[0]);
+    assert_eq!(gb.bank_data[0][0x100..0x104], gb.header.entry_point);
+    assert_eq!(gb.bank_data[1][0], 0xAB);
+}
+
+#[test]
+fn test_bank_crcs() {
+    let mut rom = make_rom(&RomSpec {
+        cartridge_type: 0x01,
+        rom_size: 0x01,
+        ..Default::default()
+    });
+    let before = load(&rom).expect("load").bank_crcs();
+    rom[2 * BANK_BYTES + 7] = 0x42;
+    let after = load(&rom).expect("load").bank_crcs();
+
+    assert_eq!(before.len(), 4);
+    assert_eq!(before[0], crc32(&rom[..BANK_BYTES]));
+    let changed: Vec<usize> = (0..4).filter(|&i| before[i] != after[i]).collect();
+    assert_eq!(changed, vec![2]);
+}
+
+#[test]
+fn test_mbc1_multicart() {
+    let mut rom = make_rom(&RomSpec {
+        cartridge_type: 0x01,
+        rom_size: 0x05,
+        ..Default::default()
+    });
+    assert!(!load(&rom).expect("load").is_mbc1_multicart());
+
+    let logo = 0x104..0x104 + NINTENDO_LOGO.len();
+    rom.copy_within(logo.clone(), 0x10 * BANK_BYTES + logo.start);
+    assert!(load(&rom).expect("load").is_mbc1_multicart());
+
+    // a truncated bank does not hold the logo
+    let mut gb = load(&rom).expect("load");
+    gb.bank_data[0x10].truncate(0x110);
+    assert!(!gb.is_mbc1_multicart());
+
+    // the same layout on a non MBC1 cartridge is not a multi-cart
+    rom[0x147] = 0x19;
+    assert!(!load(&rom).expect("load").is_mbc1_multicart());
+}
+
+#[test]
+fn test_validate() {
+    let mut gb = load(&make_rom(&RomSpec {
+        rom_size: 0x01,
+        ..Default::default()
+    }))
+    .expect("load");
+    assert!(gb.validate().is_ok());
+
+    gb.bank_data[2].truncate(100);
+    assert!(matches!(
+        gb.validate(),
+        Err(GBError::BankLengthMismatch { bank: 2, len: 100 })
+    ));
+
+    gb.bank_data.pop();
+    assert!(matches!(
+        gb.validate(),
+        Err(GBError::BankCountMismatch {
+            expected: 4,
+            actual: 3
+        })
+    ));
+}
+
+#[test]
+fn test_infer_banks_from_file_size() {
+    let opts = LoadOptions {
+        allow_size_mismatch: true,
+        ..Default::default()
+    };
+    // homebrew declaring 32 KiB but shipping 64 KiB
+    let mut rom = make_rom(&RomSpec::default());
+    rom.resize(4 * BANK_BYTES, 0x42);
+    let gb = load_with_options(&rom, opts).expect("load");
+    assert_eq!(gb.declared_banks, 2);
+    assert_eq!(gb.inferred_banks, 4);
+    assert_eq!(gb.bank_data.len(), 4);
+    assert_eq!(gb.bank_data[3][0], 0x42);
+    assert!(gb.validate().is_ok());
+
+    // a partial last bank is padded
+    rom.truncate(2 * BANK_BYTES + 10);
+    let gb = load_with_options(&rom, opts).expect("load");
+    assert_eq!(gb.inferred_banks, 3);
+    assert_eq!(gb.bank_data[2][9], 0x42);
+    assert_eq!(gb.bank_data[2][10], 0xFF);
+
+    let gb = load(&make_rom(&RomSpec::default())).expect("load");
+    assert_eq!(gb.declared_banks, gb.inferred_banks);
+}
+
+#[test]
+fn test_load_bank() {
+    let mut rom = make_rom(&RomSpec {
+        cartridge_type: 0x01,
+        rom_size: 0x01,
+        ..Default::default()
+    });
+    rom[2 * BANK_BYTES] = 0xAB;
+    let gb = load(&rom).expect("load");
+
+    for index in 0..4 {
+        assert_eq!(load_bank(&rom, index).expect("bank"), gb.bank_data[index]);
+    }
+    assert_eq!(load_bank(&rom, 2).expect("bank")[0], 0xAB);
+    assert!(matches!(
+        load_bank(&rom, 4),
+        Err(GBError::BankOutOfRange { index: 4, banks: 4 })
+    ));
+    assert!(matches!(
+        load_bank(&rom[..3 * BANK_BYTES], 3),
+        Err(GBError::NotEnoughBankData { .. })
+    ));
+}
+
+#[test]
+fn test_used_size() {
+    let mut rom = make_rom(&RomSpec {
+        cartridge_type: 0x01,
+        rom_size: 0x01,
+        ..Default::default()
+    });
+    // code ends in bank 1, the rest is padded with 0xFF
+    rom[BANK_BYTES..].fill(0xFF);
+    rom[BANK_BYTES + 0x200] = 0x42;
+    let gb = load(&rom).expect("load");
+    assert_eq!(gb.used_size(), BANK_BYTES + 0x201);
+    assert_eq!(gb.used_size_with(0xFF), BANK_BYTES + 0x201);
+    assert_eq!(gb.used_size_with(0x00), rom.len());
+
+    // zero padding, the old licensee code is the last byte set
+    let gb = load(&make_rom(&RomSpec::default())).expect("load");
+    assert_eq!(gb.used_size(), 0x14B + 1);
+
+    // no padding at all
+    let last = rom.len() - 1;
+    rom[last] = 0x42;
+    assert_eq!(load(&rom).expect("load").used_size(), rom.len());
+}
+
+#[test]
+fn test_rom_view_banks() {
+    let mut rom = make_rom(&RomSpec {
+        cartridge_type: 0x01,
+        rom_size: 0x01,
+        ..Default::default()
+    });
+    for bank in 1..4 {
+        rom[bank * BANK_BYTES..(bank + 1) * BANK_BYTES].fill(bank as u8);
+    }
+    let view = RomView::new(&rom).expect("view");
+    let gb = load(&rom).expect("load");
+
+    assert_eq!(view.num_banks(), 4);
+    assert_eq!(view.banks().count(), 4);
+    assert_eq!(gb.bank_slices().count(), 4);
+    for (i, (borrowed, copied)) in view.banks().zip(gb.bank_slices()).enumerate() {
+        assert_eq!(borrowed, &rom[i * BANK_BYTES..(i + 1) * BANK_BYTES]);
+        assert_eq!(borrowed, copied);
+        assert_eq!(view.bank(i), Some(borrowed));
+    }
+    assert!(view.bank(3).expect("bank 3").iter().all(|b| *b == 3));
+    assert_eq!(view.bank(4), None);
+}
+
+#[test]
+fn test_truncated_last_bank() {
+    let rom = make_rom(&RomSpec {
+        cartridge_type: 0x01,
+        rom_size: 0x01,
+        ..Default::default()
+    });
+    let truncated = &rom[..3 * BANK_BYTES + 0x100];
+    assert!(matches!(
+        RomView::new(truncated),
+        Err(GBError::NotEnoughBankData { expected, actual })
+            if expected == 4 * BANK_BYTES && actual == truncated.len()
+    ));
+
+    // loading it anyway pads the partial bank with 0xFF
+    let opts = LoadOptions {
+        allow_size_mismatch: true,
+        ..Default::default()
+    };
+    let gb = load_with_options(truncated, opts).expect("load");
+    let banks: Vec<&[u8]> = gb.bank_slices().collect();
+    assert_eq!(banks.len(), 4);
+    assert!(banks.iter().all(|b| b.len() == BANK_BYTES));
+    assert_eq!(
+        banks[3][..0x100],
+        rom[3 * BANK_BYTES..3 * BANK_BYTES + 0x100]
+    );
+    assert!(banks[3][0x100..].iter().all(|b| *b == 0xFF));
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "base64")]
+
+use mule_gb::base64::{decode, encode};
+
+const VECTORS: [(&str, &str); 7] = [
+    ("", ""),
+    ("f", "Zg=="),
+    ("fo", "Zm8="),
+    ("foo", "Zm9v"),
+    ("foob", "Zm9vYg=="),
+    ("fooba", "Zm9vYmE="),
+    ("foobar", "Zm9vYmFy"),
+];
+
+#[test]
+fn test_encode() {
+    for (plain, encoded) in VECTORS {
+        assert_eq!(encode(plain.as_bytes()), encoded);
+    }
+}
+
+#[test]
+fn test_decode() {
+    for (plain, encoded) in VECTORS {
+        assert_eq!(decode(encoded).as_deref(), Some(plain.as_bytes()));
+    }
+    let bank: Vec<u8> = (0..=255).cycle().take(0x4000).collect();
+    assert_eq!(decode(&encode(&bank)), Some(bank));
+}
+
+#[test]
+fn test_decode_invalid() {
+    assert_eq!(decode("Zg="), None);
+    assert_eq!(decode("Z==="), None);
+    assert_eq!(decode("Zg==Zm8="), None);
+    assert_eq!(decode("Zm-v"), None);
+}
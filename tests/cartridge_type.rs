@@ -0,0 +1,183 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{
+    CARTRIDGE_TYPES, CartridgeFeatures, CartridgeType, LoadOptions, MbcKind, load,
+    load_with_options,
+};
+
+fn cartridge_type(code: u8) -> CartridgeType {
+    let rom = make_rom(&RomSpec {
+        cartridge_type: code,
+        ram_size: 0x02,
+        ..Default::default()
+    });
+    load(&rom).expect("load").header.cartridge_type
+}
+
+#[test]
+fn test_mbc3_cartridge_types() {
+    assert!(matches!(cartridge_type(0x11), CartridgeType::MBC3));
+    assert!(matches!(cartridge_type(0x12), CartridgeType::MBC3xRAM));
+    assert!(matches!(
+        cartridge_type(0x13),
+        CartridgeType::MBC3xRAMxBattery
+    ));
+}
+
+#[test]
+fn test_cartridge_type_code() {
+    assert_eq!(CartridgeType::MBC5xRumblexRAMxBattery.code(), 0x1E);
+    assert_eq!(CartridgeType::MBC3xRAMxBattery.code(), 0x13);
+    assert_eq!(CartridgeType::MBC1xRAMxBattery.code(), 0x03);
+    assert_eq!(CartridgeType::Unknown(0x42).code(), 0x42);
+}
+
+#[test]
+fn test_cartridge_type_code_round_trip() {
+    let strict = LoadOptions {
+        strict: true,
+        ..Default::default()
+    };
+    let mut known = 0;
+    for code in 0..=255u8 {
+        let rom = make_rom(&RomSpec {
+            cartridge_type: code,
+            ..Default::default()
+        });
+        if let Ok(gb) = load_with_options(&rom, strict) {
+            assert_eq!(gb.header.cartridge_type.code(), code);
+            known += 1;
+        }
+    }
+    assert_eq!(known, 28);
+}
+
+#[test]
+fn test_cartridge_features() {
+    let ram_battery = CartridgeFeatures::RAM | CartridgeFeatures::BATTERY;
+    assert!(
+        CartridgeType::MBC3xRAMxBattery
+            .features()
+            .contains(ram_battery)
+    );
+    assert!(!CartridgeType::MBC3xRAM.features().contains(ram_battery));
+    assert!(CartridgeType::ROMOnly.features().is_empty());
+    assert_eq!(
+        CartridgeType::MBC5xRumble.features(),
+        CartridgeFeatures::RUMBLE
+    );
+}
+
+#[test]
+fn test_cartridge_features_set_operations() {
+    let mut features = CartridgeFeatures::empty();
+    assert!(features.is_empty());
+    assert!(features.contains(CartridgeFeatures::empty()));
+
+    features |= CartridgeFeatures::TIMER;
+    let union = features | CartridgeFeatures::RAM;
+    assert_eq!(union.bits(), 0b101);
+    assert!(union.contains(CartridgeFeatures::RAM));
+    assert!(union.contains(CartridgeFeatures::TIMER | CartridgeFeatures::RAM));
+    assert!(!union.contains(CartridgeFeatures::RAM | CartridgeFeatures::BATTERY));
+    assert_eq!(
+        union & (CartridgeFeatures::RAM | CartridgeFeatures::SENSOR),
+        CartridgeFeatures::RAM
+    );
+
+    let flags: Vec<CartridgeFeatures> = union.iter().collect();
+    assert_eq!(flags, [CartridgeFeatures::RAM, CartridgeFeatures::TIMER]);
+    assert_eq!(CartridgeFeatures::empty().iter().count(), 0);
+    let all = CartridgeFeatures::ALL
+        .into_iter()
+        .fold(CartridgeFeatures::empty(), |all, flag| all | flag);
+    assert_eq!(all.iter().collect::<Vec<_>>(), CartridgeFeatures::ALL);
+    assert_eq!(
+        CartridgeType::MBC7xSensorxRumblexRAMxBattery
+            .features()
+            .iter()
+            .collect::<Vec<_>>(),
+        [
+            CartridgeFeatures::RAM,
+            CartridgeFeatures::BATTERY,
+            CartridgeFeatures::RUMBLE,
+            CartridgeFeatures::SENSOR
+        ]
+    );
+}
+
+#[test]
+fn test_cartridge_features_match_has_methods() {
+    for code in 0..=255u8 {
+        let t = cartridge_type(code);
+        let features = t.features();
+        assert_eq!(features.contains(CartridgeFeatures::RAM), t.has_ram());
+        assert_eq!(
+            features.contains(CartridgeFeatures::BATTERY),
+            t.has_battery()
+        );
+        assert_eq!(features.contains(CartridgeFeatures::TIMER), t.has_timer());
+        assert_eq!(features.contains(CartridgeFeatures::RUMBLE), t.has_rumble());
+        assert_eq!(features.contains(CartridgeFeatures::SENSOR), t.has_sensor());
+    }
+}
+
+#[test]
+fn test_cartridge_types_table() {
+    assert_eq!(CARTRIDGE_TYPES.len(), 28);
+    for (code, expected) in CARTRIDGE_TYPES {
+        assert_eq!(expected.code(), *code);
+        assert_eq!(cartridge_type(*code), *expected);
+    }
+}
+
+#[test]
+fn test_has_rtc() {
+    let with_rtc: Vec<u8> = (0..=255u8)
+        .filter(|code| cartridge_type(*code).has_rtc())
+        .collect();
+    assert_eq!(with_rtc, [0x0F, 0x10]);
+    // HuC3 has a timer, but not the MBC3 clock
+    assert!(cartridge_type(0xFE).has_timer());
+    assert!(!cartridge_type(0xFE).has_rtc());
+
+    let rom = make_rom(&RomSpec {
+        cartridge_type: 0x10,
+        ram_size: 0x03,
+        ..Default::default()
+    });
+    assert!(load(&rom).expect("load").rtc_present());
+    assert!(
+        !load(&make_rom(&RomSpec::default()))
+            .expect("load")
+            .rtc_present()
+    );
+}
+
+#[test]
+fn test_mbc_kind() {
+    for (code, kind) in [
+        (0x00, MbcKind::None),
+        (0x09, MbcKind::None),
+        (0x01, MbcKind::Mbc1),
+        (0x03, MbcKind::Mbc1),
+        (0x05, MbcKind::Mbc2),
+        (0x06, MbcKind::Mbc2),
+        (0x0F, MbcKind::Mbc3),
+        (0x13, MbcKind::Mbc3),
+        (0x19, MbcKind::Mbc5),
+        (0x1E, MbcKind::Mbc5),
+        (0x20, MbcKind::Mbc6),
+        (0x22, MbcKind::Mbc7),
+        (0x0B, MbcKind::Mmm01),
+        (0x0D, MbcKind::Mmm01),
+        (0xFF, MbcKind::HuC1),
+        (0xFE, MbcKind::HuC3),
+        (0xFC, MbcKind::PocketCamera),
+        (0xFD, MbcKind::BandaiTama5),
+        (0x42, MbcKind::Unknown),
+    ] {
+        assert_eq!(cartridge_type(code).mbc_kind(), kind, "code {code:#04x}");
+    }
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::hashes::crc32;
+use mule_gb::{
+    HEADER_CHECKSUM_RANGE, HEADER_RANGE, computed_header_checksum, fix_checksums,
+    global_checksum_valid, load, load_header, verify_header_checksum,
+};
+
+// The checksums are worked out by hand, not with the functions under test.
+// The only non zero bytes of the default ROM are the entry point
+// (0xC3 + 0x50 + 0x01 = 0x114), the logo (0x1546), the title "TEST" (0x140)
+// and the old licensee code 0x01.
+//
+// header: 0 - sum(0x134..=0x14C) - 25 = -(0x140 + 0x01 + 25) = 0xA6 (mod 256)
+// global: 0x114 + 0x1546 + 0x140 + 0x01 + 0xA6 = 0x1841
+fn known_good_rom() -> Vec<u8> {
+    let mut rom = make_rom(&RomSpec::default());
+    rom[0x14D] = 0xA6;
+    rom[0x14E] = 0x18;
+    rom[0x14F] = 0x41;
+    rom
+}
+
+#[test]
+fn test_retail_header_checksum() {
+    // the checksummed header bytes of Tetris (World) (Rev 1), which stores
+    // the header checksum 0x0A
+    let rom = make_rom(&RomSpec {
+        title: b"TETRIS",
+        rom_version: 0x01,
+        ..Default::default()
+    });
+    assert_eq!(computed_header_checksum(&rom), 0x0A);
+}
+
+#[test]
+fn test_known_good_rom_checksums() {
+    let rom = known_good_rom();
+    assert!(verify_header_checksum(&rom));
+    assert!(global_checksum_valid(&rom));
+}
+
+#[test]
+fn test_global_checksum_is_big_endian() {
+    let header = load(&known_good_rom()).expect("load").header;
+    assert_eq!(header.checksum, 0xA6);
+    assert_eq!(header.global_checksum, 0x1841);
+    assert!(header.global_checksum_valid);
+}
+
+#[test]
+fn test_fix_checksums_restores_originals() {
+    let original = known_good_rom();
+    let mut rom = original.clone();
+    rom[0x14D] = 0;
+    rom[0x14E] = 0;
+    rom[0x14F] = 0;
+    assert!(!verify_header_checksum(&rom));
+
+    fix_checksums(&mut rom);
+    assert_eq!(rom, original);
+}
+
+#[test]
+fn test_fix_checksums_after_title_edit() {
+    let mut rom = known_good_rom();
+    rom[0x134..0x138].copy_from_slice(b"HACK");
+    assert!(!verify_header_checksum(&rom));
+
+    fix_checksums(&mut rom);
+    assert!(verify_header_checksum(&rom));
+    assert!(global_checksum_valid(&rom));
+}
+
+#[test]
+fn test_checksum_deltas() {
+    let mut rom = known_good_rom();
+    let header = load(&rom).expect("load").header;
+    assert_eq!(header.header_checksum_delta(&rom), 0);
+    assert_eq!(header.global_checksum_delta(&rom), 0);
+
+    // one title byte up by one lowers the header checksum by one
+    rom[0x134] += 1;
+    assert_eq!(header.header_checksum_delta(&rom), 1);
+    assert_eq!(header.global_checksum_delta(&rom), -1);
+
+    // missing bytes count as 0: the header checksum of 25 zero bytes is
+    // 0xE7 and the global sum of no bytes is 0
+    assert_eq!(
+        header.header_checksum_delta(&[0; 4]),
+        header.checksum as i16 - 0xE7
+    );
+    assert_eq!(
+        header.global_checksum_delta(&[0; 4]),
+        header.global_checksum as i32
+    );
+}
+
+#[test]
+fn test_raw_header_bytes() {
+    let rom = known_good_rom();
+    let header = load_header(&rom).expect("header");
+
+    let raw = header.raw_bytes(&rom);
+    assert_eq!(raw.len(), 0x50);
+    assert_eq!(raw, &rom[0x100..0x150]);
+    assert_eq!(crc32(raw), crc32(&rom[HEADER_RANGE]));
+    assert_eq!(header.raw_bytes(&rom[..0x120]), &rom[0x100..0x120]);
+    assert!(header.raw_bytes(&rom[..0x80]).is_empty());
+
+    let checksum = rom[HEADER_CHECKSUM_RANGE]
+        .iter()
+        .fold(0u8, |x, b| x.wrapping_sub(*b).wrapping_sub(1));
+    assert_eq!(checksum, computed_header_checksum(&rom));
+}
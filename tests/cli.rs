@@ -0,0 +1,320 @@
+#![cfg(feature = "mgb")]
+
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::hashes::{rom_hashes, to_hex};
+use mule_gb::{BANK_BYTES, fix_checksums};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("mule_gb_cli_{}_{}", std::process::id(), name))
+}
+
+fn write_rom(name: &str, rom: &[u8]) -> PathBuf {
+    let path = temp_path(name);
+    fs::write(&path, rom).expect("write");
+    path
+}
+
+fn mgb(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_mgb"))
+        .args(args)
+        .output()
+        .expect("run mgb")
+}
+
+fn mgb_with_stdin(args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mgb"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("run mgb");
+    child
+        .stdin
+        .take()
+        .expect("stdin")
+        .write_all(input)
+        .expect("write stdin");
+    child.wait_with_output().expect("wait for mgb")
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "mgb failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout.clone()).expect("utf8")
+}
+
+#[test]
+fn test_fields_subset() {
+    let path = write_rom("fields.gb", &make_rom(&RomSpec::default()));
+    let output = mgb(&[
+        path.to_str().unwrap(),
+        "--compact",
+        "--fields",
+        "game_title,manufacturer_code,rom_version",
+    ]);
+    fs::remove_file(&path).expect("cleanup");
+
+    assert_eq!(
+        stdout(&output).trim_end(),
+        r#"{"game_title":"TEST","manufacturer_code":"","rom_version":0}"#
+    );
+}
+
+#[test]
+fn test_fields_unknown_name() {
+    let path = write_rom("fields_unknown.gb", &make_rom(&RomSpec::default()));
+    let output = mgb(&[path.to_str().unwrap(), "--fields", "game_title,title"]);
+    fs::remove_file(&path).expect("cleanup");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown field title"), "{}", stderr);
+    assert!(stderr.contains("game_title"), "{}", stderr);
+}
+
+#[test]
+fn test_fields_conflicts() {
+    for flag in ["--verify", "--header-only", "--hash=crc32"] {
+        let output = mgb(&["rom.gb", "--fields", "game_title", flag]);
+        assert!(!output.status.success(), "{} accepted", flag);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
+    }
+}
+
+#[test]
+fn test_read_from_stdin() {
+    let rom = make_rom(&RomSpec::default());
+    let path = write_rom("stdin.gb", &rom);
+    let from_file = mgb(&[path.to_str().unwrap(), "--header-only"]);
+    fs::remove_file(&path).expect("cleanup");
+    let from_stdin = mgb_with_stdin(&["-", "--header-only"], &rom);
+
+    assert!(stdout(&from_stdin).contains(r#""game_title": "TEST""#));
+    assert_eq!(stdout(&from_stdin), stdout(&from_file));
+
+    let truncated = mgb_with_stdin(&["-"], &rom[..0x100]);
+    assert!(!truncated.status.success());
+}
+
+#[test]
+fn test_output_file() {
+    let path = write_rom("output.gb", &make_rom(&RomSpec::default()));
+    let out = temp_path("output.json");
+    let output = mgb(&[
+        path.to_str().unwrap(),
+        "--header-only",
+        "--output",
+        out.to_str().unwrap(),
+    ]);
+    let to_stdout = mgb(&[path.to_str().unwrap(), "--header-only"]);
+    let written = fs::read_to_string(&out).expect("read output");
+    fs::remove_file(&path).expect("cleanup");
+    fs::remove_file(&out).expect("cleanup");
+
+    assert_eq!(stdout(&output), "");
+    assert_eq!(written, stdout(&to_stdout));
+}
+
+#[test]
+fn test_header_only() {
+    let path = write_rom("header_only.gb", &make_rom(&RomSpec::default()));
+    let full = mgb(&[path.to_str().unwrap()]);
+    let header_only = mgb(&[path.to_str().unwrap(), "--header-only"]);
+    fs::remove_file(&path).expect("cleanup");
+
+    let full: serde_json::Value = serde_json::from_str(&stdout(&full)).expect("json");
+    let header: serde_json::Value = serde_json::from_str(&stdout(&header_only)).expect("json");
+    assert_eq!(full["bank_data"].as_array().map(Vec::len), Some(2));
+    assert_eq!(header["game_title"], "TEST");
+    assert!(header.get("bank_data").is_none());
+    assert_eq!(full["header"], header);
+}
+
+#[test]
+fn test_formats() {
+    let path = write_rom("formats.gb", &make_rom(&RomSpec::default()));
+    let file = path.to_str().unwrap();
+    let json = mgb(&[file, "--header-only", "--format", "json", "--compact"]);
+    let yaml = mgb(&[file, "--header-only", "--format", "yaml"]);
+    let s_expr = mgb(&[file, "--header-only", "-f", "s-expr"]);
+    fs::remove_file(&path).expect("cleanup");
+
+    assert!(stdout(&json).starts_with(r#"{"entry_point":[0,195,80,1],"#));
+    let yaml = stdout(&yaml);
+    assert!(
+        yaml.starts_with("entry_point: [0, 195, 80, 1]\n"),
+        "{}",
+        yaml
+    );
+    assert!(yaml.contains("\ngame_title: \"TEST\"\n"), "{}", yaml);
+    let s_expr = stdout(&s_expr);
+    assert!(s_expr.contains(r#"(game_title . "TEST")"#), "{}", s_expr);
+}
+
+#[test]
+fn test_verify() {
+    let mut rom = make_rom(&RomSpec::default());
+    let broken = write_rom("verify_broken.gb", &rom);
+    fix_checksums(&mut rom);
+    let fixed = write_rom("verify_fixed.gb", &rom);
+    let failed = mgb(&[broken.to_str().unwrap(), "--verify", "--compact"]);
+    let passed = mgb(&[fixed.to_str().unwrap(), "--verify", "--compact"]);
+    fs::remove_file(&broken).expect("cleanup");
+    fs::remove_file(&fixed).expect("cleanup");
+
+    assert_eq!(failed.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&failed.stdout),
+        r#"{"header_checksum_ok":false,"global_checksum_ok":false,"logo_ok":true}"#
+    );
+    assert_eq!(
+        stdout(&passed),
+        r#"{"header_checksum_ok":true,"global_checksum_ok":true,"logo_ok":true}"#
+    );
+}
+
+#[test]
+fn test_hash() {
+    let rom = make_rom(&RomSpec::default());
+    let path = write_rom("hash.gb", &rom);
+    let file = path.to_str().unwrap();
+    let crc32 = mgb(&[file, "--hash", "crc32"]);
+    let md5 = mgb(&[file, "--hash", "md5"]);
+    let sha1 = mgb(&[file, "--hash=sha1"]);
+    let unknown = mgb(&[file, "--hash", "sha256"]);
+    fs::remove_file(&path).expect("cleanup");
+
+    let hashes = rom_hashes(&rom);
+    assert_eq!(stdout(&crc32), format!("{:08x}\n", hashes.crc32));
+    assert_eq!(stdout(&md5), format!("{}\n", to_hex(&hashes.md5)));
+    assert_eq!(stdout(&sha1), format!("{}\n", to_hex(&hashes.sha1)));
+    assert!(!unknown.status.success());
+}
+
+#[test]
+fn test_scan() {
+    let dir = temp_path("scan");
+    fs::create_dir_all(dir.join("sub")).expect("create dir");
+    fs::write(dir.join("sub/good.gb"), make_rom(&RomSpec::default())).expect("write");
+    fs::write(dir.join("broken.gbc"), [0u8; 16]).expect("write");
+    fs::write(dir.join("notes.txt"), b"not a rom").expect("write");
+    let output = mgb(&["scan", dir.to_str().unwrap(), "--compact"]);
+    fs::remove_dir_all(&dir).expect("cleanup");
+
+    let summaries: serde_json::Value = serde_json::from_str(&stdout(&output)).expect("json");
+    let summaries = summaries.as_array().expect("array");
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0]["title"], "TEST");
+    assert!(
+        summaries[0]["path"]
+            .as_str()
+            .expect("path")
+            .ends_with("good.gb")
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("broken.gbc"), "{}", stderr);
+    assert!(!stderr.contains("notes.txt"), "{}", stderr);
+}
+
+#[test]
+fn test_compact() {
+    let path = write_rom("compact.gb", &make_rom(&RomSpec::default()));
+    let pretty = mgb(&[path.to_str().unwrap(), "--header-only"]);
+    let compact = mgb(&[path.to_str().unwrap(), "--header-only", "--compact"]);
+    fs::remove_file(&path).expect("cleanup");
+
+    let pretty = stdout(&pretty);
+    let compact = stdout(&compact);
+    assert!(pretty.lines().count() > 1);
+    assert_eq!(compact.lines().count(), 1);
+    assert!(compact.starts_with(r#"{"entry_point":"#));
+    let pretty: serde_json::Value = serde_json::from_str(&pretty).expect("json");
+    let compact: serde_json::Value = serde_json::from_str(&compact).expect("json");
+    assert_eq!(pretty, compact);
+}
+
+#[test]
+fn test_bank_encoding() {
+    let mut rom = make_rom(&RomSpec::default());
+    rom[BANK_BYTES] = 0xAB;
+    let path = write_rom("bank_encoding.gb", &rom);
+    let file = path.to_str().unwrap();
+    let array = mgb(&[file, "--compact", "--bank-encoding", "array"]);
+    let base64 = mgb(&[file, "--compact", "--bank-encoding", "base64"]);
+    let default = mgb(&[file, "--compact"]);
+    fs::remove_file(&path).expect("cleanup");
+
+    let array: serde_json::Value = serde_json::from_str(&stdout(&array)).expect("json");
+    let base64: serde_json::Value = serde_json::from_str(&stdout(&base64)).expect("json");
+    let default: serde_json::Value = serde_json::from_str(&stdout(&default)).expect("json");
+    assert_eq!(array["bank_data"][1][0], 0xAB);
+    assert_eq!(default["bank_data"], array["bank_data"]);
+    let banks = base64["bank_data"].as_array().expect("banks");
+    assert_eq!(banks.len(), 2);
+    for (i, bank) in banks.iter().enumerate() {
+        let decoded = mule_gb::base64::decode(bank.as_str().expect("string")).expect("base64");
+        assert_eq!(decoded, rom[i * BANK_BYTES..(i + 1) * BANK_BYTES]);
+    }
+    assert_eq!(base64["header"], array["header"]);
+}
+
+#[test]
+fn test_extract_bank() {
+    let mut rom = make_rom(&RomSpec {
+        cartridge_type: 0x01,
+        rom_size: 0x01,
+        ..Default::default()
+    });
+    rom[2 * BANK_BYTES..3 * BANK_BYTES].fill(0x42);
+    let path = write_rom("extract_bank.gb", &rom);
+    let file = path.to_str().unwrap();
+    let bank = mgb(&["extract-bank", "2", file]);
+    let out = temp_path("extract_bank.bin");
+    let to_file = mgb(&["extract-bank", "3", file, "-o", out.to_str().unwrap()]);
+    let out_of_range = mgb(&["extract-bank", "4", file]);
+    let written = fs::read(&out).expect("read output");
+    fs::remove_file(&path).expect("cleanup");
+    fs::remove_file(&out).expect("cleanup");
+
+    assert!(bank.status.success());
+    assert_eq!(bank.stdout, &rom[2 * BANK_BYTES..3 * BANK_BYTES]);
+    assert!(to_file.status.success());
+    assert_eq!(written, &rom[3 * BANK_BYTES..]);
+    assert!(!out_of_range.status.success());
+    assert!(out_of_range.stdout.is_empty());
+}
+
+#[test]
+fn test_fix_checksums() {
+    let rom = make_rom(&RomSpec::default());
+    let path = write_rom("fix_checksums.gb", &rom);
+    let output = mgb(&["fix-checksums", path.to_str().unwrap()]);
+    let fixed_path = write_rom("fix_checksums_fixed.gb", &output.stdout);
+    let again = mgb(&["fix-checksums", fixed_path.to_str().unwrap()]);
+    let too_short = mgb_with_stdin(&["fix-checksums", "-"], &rom[..0x100]);
+    fs::remove_file(&path).expect("cleanup");
+    fs::remove_file(&fixed_path).expect("cleanup");
+
+    let mut expected = rom.clone();
+    fix_checksums(&mut expected);
+    assert!(output.status.success());
+    assert_eq!(output.stdout, expected);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("header checksum: 0x00 ->"), "{}", stderr);
+    assert!(stderr.contains("global checksum: 0x0000 ->"), "{}", stderr);
+
+    // an already repaired ROM is written back unchanged and nothing is reported
+    assert_eq!(again.stdout, expected);
+    assert!(again.stderr.is_empty());
+    assert!(!too_short.status.success());
+}
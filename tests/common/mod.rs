@@ -0,0 +1,61 @@
+use mule_gb::{BANK_BYTES, NINTENDO_LOGO, ROM_SIZES};
+
+/// Header fields for a synthetic ROM built by `make_rom`.
+pub struct RomSpec {
+    pub title: &'static [u8],
+    pub manufacturer_code: &'static [u8],
+    pub gbc_flag: u8,
+    pub old_licensee_code: u8,
+    pub new_licensee_code: [u8; 2],
+    pub sgb_flag: u8,
+    pub cartridge_type: u8,
+    pub rom_size: u8,
+    pub ram_size: u8,
+    pub destination_code: u8,
+    pub rom_version: u8,
+}
+
+impl Default for RomSpec {
+    fn default() -> Self {
+        RomSpec {
+            title: b"TEST",
+            manufacturer_code: b"",
+            gbc_flag: 0x00,
+            old_licensee_code: 0x01,
+            new_licensee_code: [0, 0],
+            sgb_flag: 0x00,
+            cartridge_type: 0x00,
+            rom_size: 0x00,
+            ram_size: 0x00,
+            destination_code: 0x00,
+            rom_version: 0x00,
+        }
+    }
+}
+
+/// Lays out a ROM with the given header fields at their spec offsets and
+/// pads it to the size declared by `rom_size`. An unknown `rom_size` gets
+/// the two banks of the smallest ROM.
+pub fn make_rom(spec: &RomSpec) -> Vec<u8> {
+    let banks = ROM_SIZES
+        .iter()
+        .find(|(code, _)| *code == spec.rom_size)
+        .map_or(2, |(_, size)| size.num_banks());
+    let mut rom = vec![0; banks * BANK_BYTES];
+    rom[0x100..0x104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]);
+    rom[0x104..0x134].copy_from_slice(&NINTENDO_LOGO);
+    rom[0x134..0x134 + spec.title.len()].copy_from_slice(spec.title);
+    rom[0x13F..0x13F + spec.manufacturer_code.len()].copy_from_slice(spec.manufacturer_code);
+    if spec.gbc_flag != 0 {
+        rom[0x143] = spec.gbc_flag;
+    }
+    rom[0x144..0x146].copy_from_slice(&spec.new_licensee_code);
+    rom[0x146] = spec.sgb_flag;
+    rom[0x147] = spec.cartridge_type;
+    rom[0x148] = spec.rom_size;
+    rom[0x149] = spec.ram_size;
+    rom[0x14A] = spec.destination_code;
+    rom[0x14B] = spec.old_licensee_code;
+    rom[0x14C] = spec.rom_version;
+    rom
+}
@@ -0,0 +1,378 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{
+    CartridgeType, DestinationCode, GBCFlag, LicenseeCode, MemoryLayout, RAMSize, ROMSize, SGBFlag,
+    load, load_header,
+};
+use std::collections::HashSet;
+
+#[test]
+fn test_rom_only() {
+    let gb = load(&make_rom(&RomSpec {
+        title: b"TETRIS",
+        ..Default::default()
+    }))
+    .expect("load");
+    let header = &gb.header;
+
+    assert_eq!(gb.bank_data.len(), 2);
+    assert_eq!(header.entry_point, [0x00, 0xC3, 0x50, 0x01]);
+    assert!(header.logo_valid);
+    assert_eq!(header.game_title, "TETRIS");
+    assert!(matches!(header.gbc_flag, GBCFlag::GBOnly));
+    assert!(matches!(header.licensee_code, LicenseeCode::Nintendo));
+    assert!(matches!(header.sgb_flag, SGBFlag::NoSGB));
+    assert!(matches!(header.cartridge_type, CartridgeType::ROMOnly));
+    assert!(matches!(header.rom_size, ROMSize::NoBanking));
+    assert!(matches!(header.ram_size, RAMSize::None));
+    assert!(matches!(header.destination_code, DestinationCode::Japanese));
+}
+
+#[test]
+fn test_mbc1() {
+    let gb = load(&make_rom(&RomSpec {
+        title: b"ZELDA",
+        cartridge_type: 0x03,
+        rom_size: 0x04,
+        ram_size: 0x02,
+        destination_code: 0x01,
+        rom_version: 0x02,
+        ..Default::default()
+    }))
+    .expect("load");
+    let header = &gb.header;
+
+    assert_eq!(gb.bank_data.len(), 32);
+    assert_eq!(header.game_title, "ZELDA");
+    assert!(matches!(
+        header.cartridge_type,
+        CartridgeType::MBC1xRAMxBattery
+    ));
+    assert!(matches!(header.rom_size, ROMSize::Banks32));
+    assert!(matches!(header.ram_size, RAMSize::KB8));
+    assert!(matches!(
+        header.destination_code,
+        DestinationCode::NonJapanese
+    ));
+    assert_eq!(header.rom_version, 0x02);
+}
+
+#[test]
+fn test_mbc3() {
+    let gb = load(&make_rom(&RomSpec {
+        title: b"POKEMON GOLD",
+        cartridge_type: 0x10,
+        rom_size: 0x06,
+        ram_size: 0x03,
+        ..Default::default()
+    }))
+    .expect("load");
+    let header = &gb.header;
+
+    assert_eq!(gb.bank_data.len(), 128);
+    assert_eq!(header.game_title, "POKEMON GOLD");
+    assert!(matches!(
+        header.cartridge_type,
+        CartridgeType::MBC3xTimerxRAMxBattery
+    ));
+    assert!(header.cartridge_type.has_timer());
+    assert!(matches!(header.rom_size, ROMSize::Banks128));
+    assert!(matches!(header.ram_size, RAMSize::KB32));
+}
+
+#[test]
+fn test_mbc5() {
+    let gb = load(&make_rom(&RomSpec {
+        title: b"RUMBLE",
+        cartridge_type: 0x1C,
+        rom_size: 0x05,
+        ..Default::default()
+    }))
+    .expect("load");
+    let header = &gb.header;
+
+    assert_eq!(gb.bank_data.len(), 64);
+    assert!(matches!(header.cartridge_type, CartridgeType::MBC5xRumble));
+    assert!(header.cartridge_type.has_rumble());
+    assert!(matches!(header.rom_size, ROMSize::Banks64));
+}
+
+#[test]
+fn test_cgb() {
+    let gb = load(&make_rom(&RomSpec {
+        title: b"COLOR",
+        manufacturer_code: b"ABCE",
+        gbc_flag: 0xC0,
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"01",
+        cartridge_type: 0x1B,
+        rom_size: 0x03,
+        ram_size: 0x03,
+        ..Default::default()
+    }))
+    .expect("load");
+    let header = &gb.header;
+
+    assert_eq!(header.game_title, "COLOR");
+    assert_eq!(header.manufacturer_code, "ABCE");
+    assert!(matches!(header.gbc_flag, GBCFlag::GBCOnly));
+    assert_eq!(header.gbc_flag_raw, 0xC0);
+    assert!(matches!(header.licensee_code, LicenseeCode::Nintendo));
+    assert!(matches!(
+        header.cartridge_type,
+        CartridgeType::MBC5xRAMxBattery
+    ));
+    assert!(matches!(header.rom_size, ROMSize::Banks16));
+}
+
+#[test]
+fn test_sgb() {
+    let gb = load(&make_rom(&RomSpec {
+        title: b"SUPER",
+        gbc_flag: 0x80,
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"08",
+        sgb_flag: 0x03,
+        cartridge_type: 0x01,
+        rom_size: 0x01,
+        ..Default::default()
+    }))
+    .expect("load");
+    let header = &gb.header;
+
+    assert!(matches!(header.gbc_flag, GBCFlag::GBCAndGB));
+    assert!(matches!(header.sgb_flag, SGBFlag::SGBSupport));
+    assert!(matches!(header.licensee_code, LicenseeCode::Capcom));
+    assert!(matches!(header.cartridge_type, CartridgeType::MBC1));
+}
+
+#[test]
+fn test_battery_ram_bytes() {
+    let battery_ram_bytes = |cartridge_type, ram_size| {
+        load(&make_rom(&RomSpec {
+            cartridge_type,
+            ram_size,
+            ..Default::default()
+        }))
+        .expect("load")
+        .battery_ram_bytes()
+    };
+
+    assert_eq!(battery_ram_bytes(0x03, 0x02), 8 * 1024);
+    assert_eq!(battery_ram_bytes(0x13, 0x03), 32 * 1024);
+    assert_eq!(battery_ram_bytes(0x02, 0x02), 0);
+    assert_eq!(battery_ram_bytes(0x06, 0x00), 512);
+    assert_eq!(battery_ram_bytes(0x05, 0x00), 0);
+}
+
+#[test]
+fn test_ram_bank_count() {
+    let ram_bank_count = |ram_size| {
+        load(&make_rom(&RomSpec {
+            cartridge_type: 0x1B,
+            ram_size,
+            ..Default::default()
+        }))
+        .expect("load")
+        .ram_bank_count()
+    };
+
+    assert_eq!(ram_bank_count(0x00), 0);
+    assert_eq!(ram_bank_count(0x01), 1);
+    assert_eq!(ram_bank_count(0x02), 1);
+    assert_eq!(ram_bank_count(0x03), 4);
+    assert_eq!(ram_bank_count(0x04), 16);
+    assert_eq!(ram_bank_count(0x05), 8);
+}
+
+#[test]
+fn test_memory_layout() {
+    let memory_layout = |cartridge_type, rom_size, ram_size| {
+        load(&make_rom(&RomSpec {
+            cartridge_type,
+            rom_size,
+            ram_size,
+            ..Default::default()
+        }))
+        .expect("load")
+        .memory_layout()
+    };
+
+    assert_eq!(
+        memory_layout(0x10, 0x05, 0x03),
+        MemoryLayout {
+            rom_bytes: 1024 * 1024,
+            external_ram_bytes: 32 * 1024,
+            internal_ram_bytes: 0,
+            has_rtc: true,
+        }
+    );
+    assert_eq!(
+        memory_layout(0x06, 0x03, 0x00),
+        MemoryLayout {
+            rom_bytes: 256 * 1024,
+            external_ram_bytes: 0,
+            internal_ram_bytes: 512,
+            has_rtc: false,
+        }
+    );
+    assert_eq!(
+        memory_layout(0x00, 0x00, 0x00),
+        MemoryLayout {
+            rom_bytes: 32 * 1024,
+            external_ram_bytes: 0,
+            internal_ram_bytes: 0,
+            has_rtc: false,
+        }
+    );
+    // the HuC3 timer is not an RTC register set
+    assert_eq!(
+        memory_layout(0xFE, 0x01, 0x02),
+        MemoryLayout {
+            rom_bytes: 64 * 1024,
+            external_ram_bytes: 8 * 1024,
+            internal_ram_bytes: 0,
+            has_rtc: false,
+        }
+    );
+}
+
+#[test]
+fn test_group_by_cartridge_type() {
+    let types: HashSet<CartridgeType> = [0x01, 0x13, 0x01, 0x1B, 0x13]
+        .into_iter()
+        .map(|cartridge_type| {
+            load_header(&make_rom(&RomSpec {
+                cartridge_type,
+                ..Default::default()
+            }))
+            .expect("header")
+            .cartridge_type
+        })
+        .collect();
+
+    assert_eq!(types.len(), 3);
+    assert!(types.contains(&CartridgeType::MBC3xRAMxBattery));
+    assert_eq!(
+        load_header(&make_rom(&RomSpec::default()))
+            .expect("header")
+            .licensee_code,
+        LicenseeCode::Nintendo
+    );
+}
+
+#[test]
+fn test_cgb_compatibility() {
+    let header_for = |gbc_flag| {
+        load_header(&make_rom(&RomSpec {
+            gbc_flag,
+            ..Default::default()
+        }))
+        .expect("load")
+    };
+
+    let dmg = header_for(0x00);
+    assert!(dmg.is_dmg_only());
+    assert!(!dmg.is_cgb_enhanced());
+    assert!(!dmg.is_cgb_only());
+
+    let dual = header_for(0x80);
+    assert!(!dual.is_dmg_only());
+    assert!(dual.is_cgb_enhanced());
+    assert!(!dual.is_cgb_only());
+
+    let cgb = header_for(0xC0);
+    assert!(!cgb.is_dmg_only());
+    assert!(cgb.is_cgb_enhanced());
+    assert!(cgb.is_cgb_only());
+}
+
+#[test]
+fn test_summary() {
+    let header = load_header(&make_rom(&RomSpec {
+        title: b"SUPER MARIOLAND",
+        cartridge_type: 0x03,
+        rom_size: 0x03,
+        ram_size: 0x02,
+        gbc_flag: 0x80,
+        destination_code: 0x01,
+        ..Default::default()
+    }))
+    .expect("load");
+    assert_eq!(
+        header.summary(),
+        "SUPER MARIO (Nintendo) — MBC1+RAM+Battery, 256 KiB ROM, 8 KiB RAM, CGB+GB, Non-Japanese, v0"
+    );
+
+    let header = load_header(&make_rom(&RomSpec {
+        title: b"TETRIS",
+        sgb_flag: 0x03,
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"01",
+        ..Default::default()
+    }))
+    .expect("load");
+    assert_eq!(
+        header.summary(),
+        "TETRIS (Nintendo) — ROM only, 32 KiB ROM, no RAM, GB+SGB, Japanese, v0"
+    );
+}
+
+#[test]
+fn test_header_fields() {
+    let header = load_header(&make_rom(&RomSpec {
+        title: b"POKEMON",
+        manufacturer_code: b"AAUE",
+        gbc_flag: 0x80,
+        cartridge_type: 0x10,
+        ..Default::default()
+    }))
+    .expect("load");
+    let fields = header.fields();
+
+    assert_eq!(fields.len(), 14);
+    assert_eq!(fields[0], ("Title", String::from("POKEMON")));
+    assert_eq!(fields[1], ("Manufacturer code", String::from("AAUE")));
+    assert_eq!(
+        fields[3],
+        ("Cartridge type", String::from("MBC3+Timer+RAM+Battery"))
+    );
+    assert_eq!(fields[10], ("Entry point", String::from("00 C3 50 01")));
+
+    let lines: Vec<String> = header.to_string().lines().map(String::from).collect();
+    assert_eq!(lines.len(), fields.len());
+    assert_eq!(lines[1], "Manufacturer code: AAUE");
+
+    let header = load_header(&make_rom(&RomSpec::default())).expect("load");
+    assert_eq!(header.fields().len(), 13);
+    assert!(
+        header
+            .fields()
+            .iter()
+            .all(|(name, _)| *name != "Manufacturer code")
+    );
+}
+
+#[test]
+fn test_gbc_flag_semantics() {
+    let gbc_flag = |gbc_flag| {
+        load_header(&make_rom(&RomSpec {
+            gbc_flag,
+            ..Default::default()
+        }))
+        .expect("load")
+        .gbc_flag
+    };
+
+    assert_eq!(gbc_flag(0x00), GBCFlag::GBOnly);
+    assert_eq!(gbc_flag(0x80), GBCFlag::GBCAndGB);
+    assert_eq!(gbc_flag(0xC0), GBCFlag::GBCOnly);
+
+    assert!(GBCFlag::GBOnly.supports_dmg());
+    assert!(GBCFlag::GBCAndGB.supports_dmg());
+    assert!(!GBCFlag::GBCOnly.supports_dmg());
+    assert!(!GBCFlag::GBOnly.requires_cgb());
+    assert!(!GBCFlag::GBCAndGB.requires_cgb());
+    assert!(GBCFlag::GBCOnly.requires_cgb());
+}
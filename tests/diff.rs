@@ -0,0 +1,84 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{BANK_BYTES, load};
+
+#[test]
+fn test_diff() {
+    let spec = RomSpec {
+        cartridge_type: 0x01,
+        rom_size: 0x01,
+        ..Default::default()
+    };
+    let v1 = make_rom(&spec);
+    let mut v2 = make_rom(&RomSpec {
+        title: b"TEST 2",
+        ..spec
+    });
+    v2[0x14C] = 0x01;
+    v2[3 * BANK_BYTES + 0x42] = 0xFF;
+
+    let a = load(&v1).expect("load");
+    let b = load(&v2).expect("load");
+    assert!(a.diff(&a).is_empty());
+
+    let diff = a.diff(&b);
+    assert_eq!(
+        diff.header_fields,
+        vec!["game_title", "raw_title", "rom_version"]
+    );
+    assert_eq!(diff.banks, vec![0, 3]);
+}
+
+#[test]
+fn test_diff_raw_bytes() {
+    // the same parsed title and licensee from different bytes
+    let a = load(&make_rom(&RomSpec {
+        title: b"TEST\0A",
+        ..Default::default()
+    }))
+    .expect("load");
+    let b = load(&make_rom(&RomSpec {
+        title: b"TEST\0B",
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"01",
+        ..Default::default()
+    }))
+    .expect("load");
+
+    assert_eq!(
+        a.diff(&b).header_fields,
+        vec![
+            "raw_title",
+            "raw_old_licensee_code",
+            "raw_new_licensee_code"
+        ]
+    );
+}
+
+#[test]
+fn test_diff_bank_count() {
+    let a = load(&make_rom(&RomSpec::default())).expect("load");
+    let b = load(&make_rom(&RomSpec {
+        rom_size: 0x01,
+        ..Default::default()
+    }))
+    .expect("load");
+
+    let diff = a.diff(&b);
+    assert_eq!(diff.header_fields, vec!["rom_size"]);
+    assert_eq!(diff.banks, vec![0, 2, 3]);
+}
+
+#[test]
+fn test_diff_patched_clone() {
+    let original = load(&make_rom(&RomSpec::default())).expect("load");
+    let mut patched = original.clone();
+    patched.bank_data[1][0x10] = 0xAA;
+    patched.header.rom_version = 0x01;
+
+    assert_eq!(original.bank_data[1][0x10], 0x00);
+    let diff = original.diff(&patched);
+    assert_eq!(diff.header_fields, vec!["rom_version"]);
+    assert_eq!(diff.banks, vec![1]);
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{
+    CartridgeType, DestinationCode, GBCFlag, LicenseeCode, RAMSize, ROMSize, SGBFlag,
+    fix_checksums, load, load_header,
+};
+
+#[test]
+fn test_enum_display() {
+    assert_eq!(CartridgeType::ROMOnly.to_string(), "ROM only");
+    assert_eq!(
+        CartridgeType::MBC3xTimerxRAMxBattery.to_string(),
+        "MBC3+Timer+RAM+Battery"
+    );
+    assert_eq!(CartridgeType::PocketCamera.to_string(), "Pocket Camera");
+    assert_eq!(CartridgeType::Unknown(0x42).to_string(), "Unknown (0x42)");
+
+    assert_eq!(ROMSize::NoBanking.to_string(), "32 KiB (2 banks)");
+    assert_eq!(ROMSize::Banks64.to_string(), "1 MiB (64 banks)");
+    assert_eq!(ROMSize::Banks72.to_string(), "1152 KiB (72 banks)");
+    assert_eq!(ROMSize::Unknown(0x09).to_string(), "Unknown (0x09)");
+
+    assert_eq!(RAMSize::None.to_string(), "None");
+    assert_eq!(RAMSize::KB2.to_string(), "2 KiB");
+    assert_eq!(RAMSize::KB128.to_string(), "128 KiB");
+    assert_eq!(RAMSize::Unknown(0x07).to_string(), "Unknown (0x07)");
+
+    assert_eq!(GBCFlag::GBOnly.to_string(), "GB only");
+    assert_eq!(GBCFlag::GBCAndGB.to_string(), "GBC and GB");
+    assert_eq!(GBCFlag::GBCOnly.to_string(), "GBC only");
+
+    assert_eq!(SGBFlag::NoSGB.to_string(), "No SGB support");
+    assert_eq!(SGBFlag::SGBSupport.to_string(), "SGB support");
+    assert_eq!(SGBFlag::Unknown(0x01).to_string(), "Unknown (0x01)");
+
+    assert_eq!(DestinationCode::Japanese.to_string(), "Japanese");
+    assert_eq!(DestinationCode::NonJapanese.to_string(), "Non-Japanese");
+    assert_eq!(DestinationCode::Unknown(0x02).to_string(), "Unknown (0x02)");
+
+    assert_eq!(LicenseeCode::None.to_string(), "None");
+    assert_eq!(LicenseeCode::ElectronicArts.to_string(), "Electronic Arts");
+    assert_eq!(
+        LicenseeCode::Unknown {
+            old: Some(0xFE),
+            new: None
+        }
+        .to_string(),
+        "Unknown (0xFE)"
+    );
+}
+
+#[test]
+fn test_header_display() {
+    let mut rom = make_rom(&RomSpec {
+        cartridge_type: 0x03,
+        rom_size: 0x01,
+        ram_size: 0x02,
+        ..Default::default()
+    });
+    fix_checksums(&mut rom);
+    let header = load_header(&rom).expect("header");
+    let expected = format!(
+        "Title:             TEST\n\
+         Licensee:          Nintendo\n\
+         Cartridge type:    MBC1+RAM+Battery\n\
+         ROM size:          64 KiB (4 banks)\n\
+         RAM size:          8 KiB\n\
+         GBC:               GB only\n\
+         SGB:               No SGB support\n\
+         Destination:       Japanese\n\
+         ROM version:       0\n\
+         Entry point:       00 C3 50 01\n\
+         Logo:              valid\n\
+         Header checksum:   0x{:02X} (valid)\n\
+         Global checksum:   0x{:04X} (valid)",
+        header.checksum, header.global_checksum
+    );
+    assert_eq!(header.to_string(), expected);
+}
+
+#[test]
+fn test_header_summary() {
+    let rom = make_rom(&RomSpec {
+        title: b"super mario",
+        cartridge_type: 0x01,
+        rom_size: 0x01,
+        ..Default::default()
+    });
+    assert_eq!(
+        load(&rom).expect("load").header.summary(),
+        "SUPER MARIO (Nintendo) — MBC1, 64 KiB ROM, no RAM, GB, Japanese, v0"
+    );
+
+    let rom = make_rom(&RomSpec {
+        gbc_flag: 0xC0,
+        sgb_flag: 0x03,
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"08",
+        cartridge_type: 0x1B,
+        rom_size: 0x05,
+        ram_size: 0x03,
+        destination_code: 0x01,
+        rom_version: 2,
+        ..Default::default()
+    });
+    assert_eq!(
+        load(&rom).expect("load").header.summary(),
+        "TEST (Capcom) — MBC5+RAM+Battery, 1 MiB ROM, 32 KiB RAM, CGB+SGB, Non-Japanese, v2"
+    );
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{load, load_header};
+
+fn entry_jump_target(entry_point: [u8; 4]) -> Option<u16> {
+    let mut rom = make_rom(&RomSpec::default());
+    rom[0x100..0x104].copy_from_slice(&entry_point);
+    load_header(&rom).expect("header").entry_jump_target()
+}
+
+#[test]
+fn test_entry_jump_target() {
+    assert_eq!(entry_jump_target([0x00, 0xC3, 0x50, 0x01]), Some(0x0150));
+    assert_eq!(entry_jump_target([0xC3, 0x00, 0x02, 0x00]), Some(0x0200));
+    assert_eq!(entry_jump_target([0x18, 0x4E, 0x00, 0x00]), None);
+}
+
+#[test]
+fn test_startup_code() {
+    let rom = make_rom(&RomSpec::default());
+    let gb = load(&rom).expect("load");
+
+    assert_eq!(gb.startup_code(&rom, 4), [0x00, 0xC3, 0x50, 0x01]);
+    assert_eq!(gb.startup_code(&rom, 8), &rom[0x100..0x108]);
+    assert_eq!(gb.startup_code(&rom, 0).len(), 0);
+    assert_eq!(gb.startup_code(&rom, usize::MAX).len(), rom.len() - 0x100);
+    assert_eq!(gb.startup_code(&rom[..0x102], 4), [0x00, 0xC3]);
+    assert!(gb.startup_code(&rom[..0x80], 4).is_empty());
+}
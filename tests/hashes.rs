@@ -0,0 +1,120 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::hashes::{crc32, crc32_chunks, md5, rom_hashes, sha1, to_hex};
+use mule_gb::load;
+
+#[test]
+fn test_crc32() {
+    assert_eq!(crc32(b""), 0);
+    assert_eq!(crc32(b"123456789"), 0xCBF43926);
+}
+
+#[test]
+fn test_md5() {
+    assert_eq!(to_hex(&md5(b"")), "d41d8cd98f00b204e9800998ecf8427e");
+    assert_eq!(to_hex(&md5(b"abc")), "900150983cd24fb0d6963f7d28e17f72");
+}
+
+#[test]
+fn test_sha1() {
+    assert_eq!(
+        to_hex(&sha1(b"abc")),
+        "a9993e364706816aba3e25717850c26c9cd0d89d"
+    );
+    assert_eq!(
+        to_hex(&sha1(
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+        )),
+        "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+    );
+}
+
+#[test]
+fn test_padding_block_boundaries() {
+    // lengths around the 56 byte limit of the last block, computed with
+    // Python's hashlib
+    let cases = [
+        (
+            55,
+            "ef1772b6dff9a122358552954ad0df65",
+            "c1c8bbdc22796e28c0e15163d20899b65621d65a",
+        ),
+        (
+            56,
+            "3b0c8ac703f828b04c6c197006d17218",
+            "c2db330f6083854c99d4b5bfb6e8f29f201be699",
+        ),
+        (
+            63,
+            "b06521f39153d618550606be297466d5",
+            "03f09f5b158a7a8cdad920bddc29b81c18a551f5",
+        ),
+        (
+            64,
+            "014842d480b571495a4a0363793f7367",
+            "0098ba824b5c16427bd7a1122a5a442a25ec644d",
+        ),
+        (
+            119,
+            "8a7bd0732ed6a28ce75f6dabc90e1613",
+            "ee971065aaa017e0632a8ca6c77bb3bf8b1dfc56",
+        ),
+        (
+            120,
+            "5f61c0ccad4cac44c75ff505e1f1e537",
+            "f34c1488385346a55709ba056ddd08280dd4c6d6",
+        ),
+    ];
+    for (len, md5_hex, sha1_hex) in cases {
+        let data = vec![b'a'; len];
+        assert_eq!(to_hex(&md5(&data)), md5_hex, "md5 of {} bytes", len);
+        assert_eq!(to_hex(&sha1(&data)), sha1_hex, "sha1 of {} bytes", len);
+    }
+}
+
+#[test]
+fn test_rom_hashes_multi_block() {
+    let data = vec![0xFFu8; 32 * 1024];
+    let hashes = rom_hashes(&data);
+    assert_eq!(hashes.crc32, 0x1B43EABD);
+    assert_eq!(to_hex(&hashes.md5), "3df7b33399422731e8e5615785c0536d");
+    assert_eq!(
+        to_hex(&hashes.sha1),
+        "ca711c69165e1fa5be72993b9a7870ef6d485249"
+    );
+}
+
+#[test]
+fn test_crc32_chunks() {
+    assert_eq!(crc32_chunks([&b"1234"[..], b"", b"56789"]), 0xCBF43926);
+}
+
+#[test]
+fn test_identify_with() {
+    let rom = make_rom(&RomSpec::default());
+    let gb = load(&rom).expect("load");
+    let known = crc32(&rom);
+    assert_eq!(gb.crc32(), known);
+
+    let lookup = |crc| (crc == known).then(|| "Test (World)".to_string());
+    assert_eq!(gb.identify_with(lookup).as_deref(), Some("Test (World)"));
+    assert_eq!(gb.identify_with(|_| None), None);
+}
+
+#[test]
+fn test_fingerprint() {
+    let mut rom = make_rom(&RomSpec {
+        title: b"Tetris ",
+        cartridge_type: 0x01,
+        rom_size: 0x01,
+        ..Default::default()
+    });
+    rom[0x14E] = 0xBE;
+    rom[0x14F] = 0xEF;
+    let gb = load(&rom).expect("load");
+    assert_eq!(gb.fingerprint(), "BEEF-01-01-TETRIS");
+
+    rom[0x4000] = 0xFF;
+    assert_eq!(load(&rom).expect("load").fingerprint(), gb.fingerprint());
+}
@@ -0,0 +1,307 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{
+    CartridgeType, GBError, HeaderRef, LicenseeCode, LoadOptions, ROMSize, SGBFlag, fix_checksums,
+    load, load_at_offset, load_header, load_with_options,
+};
+
+#[test]
+fn test_load_too_short() {
+    let data = [0u8; 16];
+    assert!(matches!(load(&data), Err(GBError::TooShort { len: 16 })));
+    assert!(matches!(
+        load_header(&data),
+        Err(GBError::TooShort { len: 16 })
+    ));
+}
+
+#[test]
+fn test_load_header_only() {
+    let rom = make_rom(&RomSpec::default());
+    let header = load_header(&rom[..0x150]).expect("header");
+    assert_eq!(header.game_title, "TEST");
+}
+
+#[test]
+fn test_load_lenient_unknown_values() {
+    let mut rom = make_rom(&RomSpec::default());
+    rom[0x147] = 0x42;
+    let binary = load(&rom).expect("lenient load");
+    assert!(matches!(
+        binary.header.cartridge_type,
+        CartridgeType::Unknown(0x42)
+    ));
+}
+
+#[test]
+fn test_load_strict_unknown_values() {
+    let strict = LoadOptions {
+        strict: true,
+        ..Default::default()
+    };
+    let mut rom = make_rom(&RomSpec::default());
+    rom[0x147] = 0x42;
+    assert!(matches!(
+        load_with_options(&rom, strict),
+        Err(GBError::UnsupportedCartridgeType {
+            value: 0x42,
+            offset: 0x147
+        })
+    ));
+
+    let mut rom = make_rom(&RomSpec::default());
+    rom[0x148] = 0x09;
+    assert!(matches!(
+        load_with_options(&rom, strict),
+        Err(GBError::UnsupportedRomSize {
+            value: 0x09,
+            offset: 0x148
+        })
+    ));
+}
+
+#[test]
+fn test_load_lenient_unknown_rom_size() {
+    let mut rom = make_rom(&RomSpec::default());
+    rom[0x148] = 0x09;
+    let opts = LoadOptions {
+        allow_size_mismatch: true,
+        ..Default::default()
+    };
+    let binary = load_with_options(&rom, opts).expect("lenient load");
+    assert!(matches!(binary.header.rom_size, ROMSize::Unknown(0x09)));
+
+    // without a declared size there is nothing to check the length against
+    assert!(matches!(
+        load(&rom),
+        Err(GBError::UnsupportedRomSize {
+            value: 0x09,
+            offset: 0x148
+        })
+    ));
+}
+
+#[test]
+fn test_load_unknown_sgb_flag() {
+    let strict = LoadOptions {
+        strict: true,
+        ..Default::default()
+    };
+    for flag in [0x01, 0x02, 0x04, 0xFF] {
+        let rom = make_rom(&RomSpec {
+            sgb_flag: flag,
+            ..Default::default()
+        });
+        let header = load(&rom).expect("lenient load").header;
+        assert_eq!(header.sgb_flag, SGBFlag::Unknown(flag));
+        assert!(matches!(
+            load_with_options(&rom, strict),
+            Err(GBError::UnsupportedSgbFlag { value, offset: 0x146 }) if value == flag
+        ));
+    }
+}
+
+#[test]
+fn test_load_verify_checksums() {
+    let opts = LoadOptions {
+        verify_checksums: true,
+        ..Default::default()
+    };
+    let mut rom = make_rom(&RomSpec::default());
+    assert!(matches!(
+        load_with_options(&rom, opts),
+        Err(GBError::HeaderChecksumMismatch { .. })
+    ));
+    fix_checksums(&mut rom);
+    assert!(load_with_options(&rom, opts).is_ok());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_load_from_reader() {
+    use mule_gb::load_from_reader;
+    use std::io::{self, Cursor, Read};
+
+    let rom = make_rom(&RomSpec {
+        cartridge_type: 0x01,
+        rom_size: 0x01,
+        ..Default::default()
+    });
+    let from_reader = load_from_reader(Cursor::new(&rom)).expect("load from reader");
+    let from_slice = load(&rom).expect("load");
+    assert_eq!(from_reader.header.game_title, from_slice.header.game_title);
+    assert_eq!(from_reader.bank_data, from_slice.bank_data);
+    assert_eq!(from_reader.to_bytes(), rom);
+
+    // a reader that fails after handing out the header
+    struct Failing<'a>(&'a [u8]);
+    impl Read for Failing<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() {
+                return Err(io::Error::other("disconnected"));
+            }
+            self.0.read(buf)
+        }
+    }
+    let result = load_from_reader(Failing(&rom[..0x200]));
+    assert!(matches!(result, Err(GBError::Io(e)) if e.to_string() == "disconnected"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_field_offsets() {
+    let rom = make_rom(&RomSpec {
+        cartridge_type: 0x01,
+        ..Default::default()
+    });
+    let offsets = load_header(&rom).expect("header").field_offsets();
+
+    assert_eq!(offsets["game_title"], 0x134);
+    assert_eq!(offsets["cartridge_type"], 0x147);
+    assert_eq!(rom[offsets["cartridge_type"]], 0x01);
+    assert_eq!(offsets["checksum"], 0x14D);
+    assert!(!offsets.contains_key("manufacturer_code"));
+}
+
+#[test]
+fn test_file_size_delta() {
+    let opts = LoadOptions {
+        allow_size_mismatch: true,
+        ..Default::default()
+    };
+    let mut rom = make_rom(&RomSpec::default());
+    let gb = load(&rom).expect("load");
+    assert_eq!(gb.file_size_delta(&rom), 0);
+
+    rom.extend_from_slice(&[0xFF; 512]);
+    let gb = load_with_options(&rom, opts).expect("overdump");
+    assert_eq!(gb.file_size_delta(&rom), 512);
+    assert_eq!(gb.file_size_delta(&rom[..0x4000]), -0x4000);
+}
+
+#[test]
+fn test_trailer() {
+    let mut rom = make_rom(&RomSpec::default());
+    let gb = load(&rom).expect("load");
+    assert!(gb.trailer(&rom).is_empty());
+    assert!(gb.trailer(&rom[..0x4000]).is_empty());
+
+    rom.extend_from_slice(b"PATCHEOF");
+    let opts = LoadOptions {
+        allow_size_mismatch: true,
+        ..Default::default()
+    };
+    let gb = load_with_options(&rom, opts).expect("overdump");
+    assert_eq!(gb.trailer(&rom), b"PATCHEOF");
+}
+
+#[test]
+fn test_load_at_offset() {
+    let rom = make_rom(&RomSpec {
+        title: b"EMBEDDED",
+        ..Default::default()
+    });
+    let mut blob = vec![0xEE; 0x200];
+    blob.extend_from_slice(&rom);
+    blob.extend_from_slice(&[0xEE; 0x100]);
+
+    let gb = load_at_offset(&blob, 0x200).expect("load");
+    assert_eq!(gb.header.game_title, "EMBEDDED");
+    assert_eq!(gb.bank_data.concat(), rom);
+
+    assert!(matches!(
+        load_at_offset(&blob[..0x200 + rom.len() - 1], 0x200),
+        Err(GBError::SizeMismatch { .. })
+    ));
+    assert!(matches!(
+        load_at_offset(&blob, blob.len() + 1),
+        Err(GBError::TooShort { len: 0 })
+    ));
+}
+
+#[test]
+fn test_unknown_bytes() {
+    let mut rom = make_rom(&RomSpec::default());
+    assert!(
+        load_header(&rom)
+            .expect("header")
+            .unknown_bytes()
+            .is_empty()
+    );
+
+    rom[0x143] = 0x88;
+    rom[0x146] = 0x01;
+    rom[0x147] = 0x23;
+    rom[0x149] = 0x07;
+    rom[0x14A] = 0x02;
+    rom[0x14B] = 0xF1;
+    assert_eq!(
+        load_header(&rom).expect("header").unknown_bytes(),
+        vec![
+            ("gbc_flag", 0x88),
+            ("sgb_flag", 0x01),
+            ("cartridge_type", 0x23),
+            ("ram_size", 0x07),
+            ("destination_code", 0x02),
+            ("old_licensee_code", 0xF1),
+        ]
+    );
+
+    let mut rom = make_rom(&RomSpec {
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"ZZ",
+        ..Default::default()
+    });
+    rom[0x148] = 0x09;
+    assert_eq!(
+        load_header(&rom).expect("header").unknown_bytes(),
+        vec![
+            ("new_licensee_code", b'Z'),
+            ("new_licensee_code", b'Z'),
+            ("rom_size", 0x09),
+        ]
+    );
+}
+
+#[test]
+fn test_header_ref() {
+    let mut rom = make_rom(&RomSpec {
+        title: b"CGBGAME",
+        manufacturer_code: b"ABCD",
+        gbc_flag: 0x80,
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"08",
+        cartridge_type: 0x13,
+        rom_size: 0x01,
+        ram_size: 0x03,
+        destination_code: 0x01,
+        rom_version: 0x02,
+        ..Default::default()
+    });
+    fix_checksums(&mut rom);
+    let header = load_header(&rom).expect("header");
+    let header_ref = HeaderRef::new(&rom).expect("header ref");
+
+    assert_eq!(header_ref.title(), header.game_title.as_bytes());
+    assert_eq!(
+        header_ref.manufacturer_code(),
+        header.manufacturer_code.as_bytes()
+    );
+    assert_eq!(header_ref.raw_title(), header.raw_title);
+    assert_eq!(header_ref.gbc_flag(), header.gbc_flag);
+    assert_eq!(header_ref.licensee_code(), LicenseeCode::Capcom);
+    assert_eq!(header_ref.cartridge_type(), header.cartridge_type);
+    assert_eq!(header_ref.rom_size(), header.rom_size);
+    assert_eq!(header_ref.ram_size(), header.ram_size);
+    assert_eq!(header_ref.destination_code(), header.destination_code);
+    assert_eq!(header_ref.rom_version(), 0x02);
+    assert!(header_ref.logo_valid());
+    assert!(header_ref.checksum_valid());
+    assert_eq!(header_ref.global_checksum(), header.global_checksum);
+
+    assert!(matches!(
+        HeaderRef::new(&rom[..0x14F]),
+        Err(GBError::TooShort { len: 0x14F })
+    ));
+}
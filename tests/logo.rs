@@ -0,0 +1,23 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{LogoStatus, load_header};
+
+fn logo_kind(offset: usize) -> LogoStatus {
+    let mut rom = make_rom(&RomSpec::default());
+    rom[0x104 + offset] ^= 0xFF;
+    load_header(&rom).expect("header").logo_kind()
+}
+
+#[test]
+fn test_logo_kind() {
+    let rom = make_rom(&RomSpec::default());
+    let header = load_header(&rom).expect("header");
+    assert_eq!(header.logo_kind(), LogoStatus::Valid);
+    assert!(header.logo_valid);
+
+    assert_eq!(logo_kind(0), LogoStatus::Corrupt);
+    assert_eq!(logo_kind(23), LogoStatus::Corrupt);
+    assert_eq!(logo_kind(24), LogoStatus::NonStandard);
+    assert_eq!(logo_kind(47), LogoStatus::NonStandard);
+}
@@ -0,0 +1,29 @@
+#![cfg(all(
+    feature = "mmap",
+    target_pointer_width = "64",
+    any(target_os = "linux", target_os = "macos")
+))]
+
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{GBError, load_mmap};
+use std::fs;
+
+#[test]
+fn test_load_mmap() {
+    let dir = std::env::temp_dir();
+    let path = dir.join(format!("mule_gb_mmap_{}.gb", std::process::id()));
+    let empty = dir.join(format!("mule_gb_mmap_{}_empty.gb", std::process::id()));
+    fs::write(&path, make_rom(&RomSpec::default())).expect("write");
+    fs::write(&empty, []).expect("write");
+
+    let gb = load_mmap(&path);
+    let empty_result = load_mmap(&empty);
+    fs::remove_file(&path).expect("cleanup");
+    fs::remove_file(&empty).expect("cleanup");
+
+    assert_eq!(gb.expect("load").header.game_title, "TEST");
+    assert!(matches!(empty_result, Err(GBError::TooShort { len: 0 })));
+    assert!(matches!(load_mmap(&path), Err(GBError::Io(_))));
+}
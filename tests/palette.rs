@@ -0,0 +1,57 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::load_header;
+
+fn uses_builtin_palette(spec: RomSpec) -> bool {
+    load_header(&make_rom(&spec))
+        .expect("header")
+        .uses_builtin_palette()
+}
+
+#[test]
+fn test_builtin_palette() {
+    assert!(uses_builtin_palette(RomSpec {
+        title: b"TETRIS",
+        ..Default::default()
+    }));
+    assert!(uses_builtin_palette(RomSpec {
+        title: b"ZELDA",
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"01",
+        ..Default::default()
+    }));
+}
+
+#[test]
+fn test_no_builtin_palette() {
+    // unknown title hash
+    assert!(!uses_builtin_palette(RomSpec {
+        title: b"TEST",
+        ..Default::default()
+    }));
+    // not a Nintendo game
+    assert!(!uses_builtin_palette(RomSpec {
+        title: b"TETRIS",
+        old_licensee_code: 0x08,
+        ..Default::default()
+    }));
+    // other Nintendo licensee codes are not checked for by the boot ROM
+    assert!(!uses_builtin_palette(RomSpec {
+        title: b"TETRIS",
+        old_licensee_code: 0x31,
+        ..Default::default()
+    }));
+    assert!(!uses_builtin_palette(RomSpec {
+        title: b"TETRIS",
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"31",
+        ..Default::default()
+    }));
+    // CGB games bring their own palettes
+    assert!(!uses_builtin_palette(RomSpec {
+        title: b"TETRIS",
+        gbc_flag: 0x80,
+        ..Default::default()
+    }));
+}
@@ -0,0 +1,151 @@
+use mule_gb::GBError;
+use mule_gb::reader::DataReader;
+
+#[test]
+fn test_read_bool_reads_one_byte() {
+    let data = [0x01, 0x00, 0x02];
+    let mut reader = DataReader::new(&data);
+    assert!(reader.read_bool());
+    assert_eq!(reader.offset(), 1);
+    assert!(!reader.read_bool());
+    assert!(reader.read_bool());
+}
+
+#[test]
+fn test_read_big_endian() {
+    let data = [0x18, 0x41, 0x01, 0x02, 0x03, 0x04];
+    let mut reader = DataReader::new(&data);
+    assert_eq!(reader.read_u16_be(), 0x1841);
+    assert_eq!(reader.read_u32_be(), 0x01020304);
+    assert!(reader.try_read_u16_be().is_err());
+}
+
+#[test]
+fn test_read_utf8_string_trimmed() {
+    let data = *b"ZELDA\0XY\0\0ABC";
+    let mut reader = DataReader::new(&data);
+    assert_eq!(reader.read_utf8_string_trimmed(10), "ZELDA");
+    assert_eq!(reader.offset(), 10);
+    assert_eq!(reader.read_utf8_string_trimmed(3), "ABC");
+    assert!(reader.try_read_utf8_string_trimmed(1).is_err());
+
+    // an unterminated field is read in full, invalid UTF-8 is replaced
+    let data = [b'A', 0xFF, b'B', b'C', 0x00];
+    let mut reader = DataReader::new(&data);
+    assert_eq!(reader.read_utf8_string_trimmed(3), "A\u{FFFD}B");
+    assert!(matches!(
+        reader.try_read_utf8_string_trimmed(3),
+        Err(GBError::UnexpectedEof {
+            offset: 3,
+            needed: 3
+        })
+    ));
+    assert_eq!(reader.offset(), 3);
+    assert_eq!(reader.try_read_utf8_string_trimmed(2).expect("string"), "C");
+}
+
+#[test]
+fn test_bytes() {
+    let data = [0x01, 0x02, 0x03, 0x04, 0x05];
+    let mut reader = DataReader::new(&data);
+    reader.skip(1);
+    let taken: Vec<u8> = reader.bytes().take(2).collect();
+    assert_eq!(taken, [0x02, 0x03]);
+    assert_eq!(reader.offset(), 3);
+    assert_eq!(reader.bytes().collect::<Vec<_>>(), [0x04, 0x05]);
+    assert_eq!(reader.bytes().next(), None);
+    assert_eq!(reader.offset(), 5);
+}
+
+#[test]
+fn test_try_read_u8_at() {
+    let data = [0x01, 0x02];
+    let reader = DataReader::new(&data);
+    assert_eq!(reader.try_read_u8_at(1), Some(0x02));
+    assert_eq!(reader.try_read_u8_at(2), None);
+    assert_eq!(reader.try_read_u8_at(usize::MAX), None);
+    assert_eq!(reader.offset(), 0);
+}
+
+#[test]
+fn test_read_bytes() {
+    let data = [0x01, 0x02, 0x03, 0x04];
+    let mut reader = DataReader::new(&data);
+    assert_eq!(reader.read_bytes(2), [0x01, 0x02]);
+    assert_eq!(reader.offset(), 2);
+    assert_eq!(reader.try_read_bytes(2).expect("bytes"), [0x03, 0x04]);
+
+    // at the end only empty reads succeed
+    assert_eq!(reader.read_bytes(0), []);
+    assert!(matches!(
+        reader.try_read_bytes(1),
+        Err(GBError::UnexpectedEof {
+            offset: 4,
+            needed: 1
+        })
+    ));
+    assert_eq!(reader.offset(), 4);
+
+    let mut reader = DataReader::new(&data);
+    reader.skip(1);
+    assert!(matches!(
+        reader.try_read_bytes(4),
+        Err(GBError::UnexpectedEof {
+            offset: 1,
+            needed: 4
+        })
+    ));
+    assert!(reader.try_read_bytes(usize::MAX).is_err());
+    assert_eq!(reader.offset(), 1);
+}
+
+#[test]
+fn test_peek() {
+    let data = [0x34, 0x12, 0x56];
+    let mut reader = DataReader::new(&data);
+    assert_eq!(reader.peek_u8(), 0x34);
+    assert_eq!(reader.peek_u16(), 0x1234);
+    assert_eq!(reader.peek_bytes(3), data);
+    assert_eq!(reader.offset(), 0);
+    assert_eq!(reader.read_u16(), 0x1234);
+
+    assert_eq!(reader.try_peek_u8(), Some(0x56));
+    assert_eq!(reader.try_peek_u16(), None);
+    assert_eq!(reader.try_peek_bytes(1), Some(&[0x56][..]));
+    assert_eq!(reader.try_peek_bytes(2), None);
+    assert_eq!(reader.try_peek_bytes(usize::MAX), None);
+
+    reader.skip(1);
+    assert_eq!(reader.try_peek_u8(), None);
+    assert_eq!(reader.try_peek_bytes(0), Some(&[][..]));
+    assert_eq!(reader.offset(), 3);
+}
+
+#[test]
+fn test_seek_rewind_remaining() {
+    let data = [0x01, 0x02, 0x03, 0x04, 0x05];
+    let mut reader = DataReader::new(&data);
+    assert_eq!(reader.remaining(), 5);
+
+    reader.seek(3);
+    assert_eq!(reader.offset(), 3);
+    assert_eq!(reader.remaining(), 2);
+    assert_eq!(reader.read_u8(), 0x04);
+
+    reader.rewind(3);
+    assert_eq!(reader.offset(), 1);
+    assert_eq!(reader.remaining(), 4);
+    assert_eq!(reader.read_u8(), 0x02);
+
+    reader.seek(5);
+    assert_eq!(reader.remaining(), 0);
+    assert!(reader.try_read_u8().is_err());
+
+    // seeking past the end leaves nothing to read instead of underflowing
+    reader.seek(8);
+    assert_eq!(reader.remaining(), 0);
+    assert!(reader.try_read_u8().is_err());
+    reader.rewind(8);
+    assert_eq!(reader.offset(), 0);
+    assert_eq!(reader.remaining(), 5);
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{Region, load_header};
+
+fn region(spec: RomSpec) -> Region {
+    load_header(&make_rom(&spec))
+        .expect("header")
+        .likely_region()
+}
+
+#[test]
+fn test_japanese_destination() {
+    assert_eq!(region(RomSpec::default()), Region::Japan);
+    // the destination code wins over the manufacturer code
+    assert_eq!(
+        region(RomSpec {
+            gbc_flag: 0x80,
+            manufacturer_code: b"AXVE",
+            ..Default::default()
+        }),
+        Region::Japan
+    );
+}
+
+#[test]
+fn test_manufacturer_code_region() {
+    let with_code = |manufacturer_code| {
+        region(RomSpec {
+            gbc_flag: 0x80,
+            manufacturer_code,
+            destination_code: 0x01,
+            ..Default::default()
+        })
+    };
+    assert_eq!(with_code(b"AXVE"), Region::Usa);
+    assert_eq!(with_code(b"AXVP"), Region::Europe);
+    assert_eq!(with_code(b"AXVD"), Region::Europe);
+}
+
+#[test]
+fn test_licensee_region() {
+    let with_licensee = |old_licensee_code| {
+        region(RomSpec {
+            old_licensee_code,
+            destination_code: 0x01,
+            ..Default::default()
+        })
+    };
+    // Ocean
+    assert_eq!(with_licensee(0x67), Region::Europe);
+    // Nintendo
+    assert_eq!(with_licensee(0x01), Region::Usa);
+}
+
+#[test]
+fn test_unknown_region() {
+    let mut rom = make_rom(&RomSpec::default());
+    rom[0x14A] = 0x02;
+    assert_eq!(
+        load_header(&rom).expect("header").likely_region(),
+        Region::Unknown
+    );
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{LoadOptions, load, load_header, load_with_options};
+
+const LENIENT: LoadOptions = LoadOptions {
+    strict: false,
+    verify_checksums: false,
+    allow_size_mismatch: true,
+};
+
+// A fixed xorshift generator, so failures are reproducible.
+fn random_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+#[test]
+fn test_truncated_rom_does_not_panic() {
+    let rom = make_rom(&RomSpec {
+        cartridge_type: 0x13,
+        rom_size: 0x01,
+        ram_size: 0x03,
+        ..Default::default()
+    });
+    for len in 0..0x200 {
+        let _ = load(&rom[..len]);
+        let _ = load_header(&rom[..len]);
+        let _ = load_with_options(&rom[..len], LENIENT);
+    }
+}
+
+#[test]
+fn test_random_headers_do_not_panic() {
+    let rom = make_rom(&RomSpec::default());
+    for seed in 1..512 {
+        let mut data = rom.clone();
+        data[0x100..0x150].copy_from_slice(&random_bytes(seed, 0x50));
+        let _ = load(&data);
+        let _ = load_with_options(&data, LENIENT);
+        let _ = load_with_options(&data[..0x4000], LENIENT);
+    }
+}
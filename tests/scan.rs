@@ -0,0 +1,66 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::GBError;
+use mule_gb::scan::scan_dir;
+use std::fs;
+
+#[test]
+fn test_scan_dir() {
+    let dir = std::env::temp_dir().join(format!("mule_gb_scan_{}", std::process::id()));
+    fs::create_dir_all(dir.join("sub")).expect("create dir");
+    fs::write(dir.join("a.gb"), make_rom(&RomSpec::default())).expect("write");
+    fs::write(dir.join("sub").join("b.gbc"), [0u8; 16]).expect("write");
+    fs::write(dir.join("notes.txt"), b"not a rom").expect("write");
+
+    let entries = scan_dir(&dir).expect("scan");
+    fs::remove_dir_all(&dir).expect("cleanup");
+
+    assert_eq!(entries.len(), 2);
+    assert!(entries[0].path.ends_with("a.gb"));
+    assert_eq!(
+        entries[0].header.as_ref().expect("header").game_title,
+        "TEST"
+    );
+    assert!(entries[1].path.ends_with("sub/b.gbc"));
+    assert!(matches!(
+        entries[1].header,
+        Err(GBError::TooShort { len: 16 })
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_scan_dir_symlink_loop_and_unreadable_dir() {
+    use std::os::unix::fs::{PermissionsExt, symlink};
+
+    let dir = std::env::temp_dir().join(format!("mule_gb_scan_loop_{}", std::process::id()));
+    let locked = dir.join("locked");
+    fs::create_dir_all(&locked).expect("create dir");
+    fs::write(dir.join("a.gb"), make_rom(&RomSpec::default())).expect("write");
+    fs::write(locked.join("b.gb"), make_rom(&RomSpec::default())).expect("write");
+    symlink(&dir, dir.join("loop")).expect("symlink");
+    symlink(dir.join("missing"), dir.join("dangling.gb")).expect("symlink");
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).expect("chmod");
+    // root can read the directory anyway
+    let locked_readable = fs::read_dir(&locked).is_ok();
+
+    let entries = scan_dir(&dir);
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).expect("chmod");
+    fs::remove_dir_all(&dir).expect("cleanup");
+
+    let entries = entries.expect("scan");
+    assert_eq!(entries.len(), 3);
+    assert!(entries[0].path.ends_with("a.gb"));
+    assert!(entries[0].header.is_ok());
+    assert!(entries[1].path.ends_with("dangling.gb"));
+    assert!(matches!(entries[1].header, Err(GBError::Io(_))));
+    if locked_readable {
+        assert!(entries[2].path.ends_with("locked/b.gb"));
+    } else {
+        assert!(entries[2].path.ends_with("locked"));
+        assert!(matches!(entries[2].header, Err(GBError::Io(_))));
+    }
+}
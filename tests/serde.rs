@@ -0,0 +1,82 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{GBBinary, load};
+
+#[test]
+fn test_json_round_trip() {
+    let rom = make_rom(&RomSpec {
+        title: b"ROUNDTRIP",
+        manufacturer_code: b"ABCD",
+        gbc_flag: 0x80,
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"01",
+        cartridge_type: 0x13,
+        rom_size: 0x01,
+        ram_size: 0x03,
+        ..Default::default()
+    });
+    let gb = load(&rom).expect("load");
+
+    let json = serde_json::to_string(&gb).expect("serialise");
+    let parsed: GBBinary = serde_json::from_str(&json).expect("deserialise");
+    assert_eq!(parsed.to_bytes(), rom);
+    assert_eq!(parsed.header.game_title, "ROUNDTRIP");
+    assert_eq!(parsed.declared_banks, gb.declared_banks);
+    assert_eq!(parsed.inferred_banks, gb.inferred_banks);
+    assert_eq!(serde_json::to_string(&parsed).expect("serialise"), json);
+}
+
+#[cfg(not(feature = "base64"))]
+#[test]
+fn test_json_bank_data_arrays() {
+    let rom = make_rom(&RomSpec::default());
+    let json = serde_json::to_value(load(&rom).expect("load")).expect("serialise");
+    let banks = json["bank_data"].as_array().expect("array of banks");
+    assert_eq!(banks.len(), 2);
+    assert_eq!(banks[0][0x134], 'T' as u64);
+
+    let parsed: GBBinary = serde_json::from_value(json).expect("deserialise");
+    assert_eq!(parsed.to_bytes(), rom);
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn test_json_bank_data_base64() {
+    let rom = make_rom(&RomSpec::default());
+    let json = serde_json::to_value(load(&rom).expect("load")).expect("serialise");
+    let banks = json["bank_data"].as_array().expect("array of banks");
+    assert_eq!(banks.len(), 2);
+    assert_eq!(
+        banks[0].as_str().expect("base64 string"),
+        mule_gb::base64::encode(&rom[..mule_gb::BANK_BYTES])
+    );
+
+    let parsed: GBBinary = serde_json::from_value(json).expect("deserialise");
+    assert_eq!(parsed.to_bytes(), rom);
+}
+
+#[test]
+fn test_json_without_later_fields() {
+    let gb = load(&make_rom(&RomSpec::default())).expect("load");
+    let mut json = serde_json::to_value(&gb).expect("serialise");
+    for key in ["declared_banks", "inferred_banks"] {
+        json.as_object_mut().unwrap().remove(key);
+    }
+    let header = json["header"].as_object_mut().unwrap();
+    for key in [
+        "raw_title",
+        "gbc_flag_raw",
+        "raw_old_licensee_code",
+        "raw_new_licensee_code",
+    ] {
+        header.remove(key);
+    }
+
+    let parsed: GBBinary = serde_json::from_value(json).expect("deserialise");
+    assert_eq!(parsed.declared_banks, 0);
+    assert_eq!(parsed.header.raw_title, [0; 16]);
+    assert_eq!(parsed.header.game_title, "TEST");
+}
@@ -0,0 +1,42 @@
+#![cfg(all(feature = "serde-kebab-case", feature = "mgb"))]
+
+use mule_gb::{
+    CartridgeType, DestinationCode, GBCFlag, LicenseeCode, MbcKind, RAMSize, ROMSize, SGBFlag,
+    Warning,
+};
+use serde::Serialize;
+
+fn name<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).expect("json")
+}
+
+#[test]
+fn test_kebab_case_variants() {
+    assert_eq!(
+        name(&CartridgeType::MBC5xRumblexRAMxBattery),
+        "\"mbc5-rumble-ram-battery\""
+    );
+    assert_eq!(name(&CartridgeType::ROMOnly), "\"rom-only\"");
+    assert_eq!(
+        name(&CartridgeType::HuC1xRAMxBattery),
+        "\"huc1-ram-battery\""
+    );
+    assert_eq!(name(&CartridgeType::PocketCamera), "\"pocket-camera\"");
+    assert_eq!(name(&CartridgeType::Unknown(0xFF)), "{\"unknown\":255}");
+    assert_eq!(name(&GBCFlag::GBCAndGB), "\"gbc-and-gb\"");
+    assert_eq!(name(&SGBFlag::NoSGB), "\"no-sgb\"");
+    assert_eq!(name(&LicenseeCode::HALLaboratory), "\"hal-laboratory\"");
+    assert_eq!(name(&LicenseeCode::ElectronicArts), "\"electronic-arts\"");
+    assert_eq!(name(&MbcKind::HuC3), "\"huc3\"");
+    assert_eq!(name(&ROMSize::NoBanking), "\"no-banking\"");
+    assert_eq!(name(&RAMSize::KB128), "\"kb128\"");
+    assert_eq!(name(&DestinationCode::NonJapanese), "\"non-japanese\"");
+    assert_eq!(name(&Warning::Mbc2ExternalRam), "\"mbc2-external-ram\"");
+}
+
+#[test]
+fn test_kebab_case_round_trip() {
+    let json = name(&CartridgeType::MBC3xTimerxRAMxBattery);
+    let parsed: CartridgeType = serde_json::from_str(&json).expect("parse");
+    assert_eq!(parsed, CartridgeType::MBC3xTimerxRAMxBattery);
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "sgb")]
+
+use mule_gb::sgb::{SgbCommand, extract_sgb_packets};
+
+fn packet(bytes: &[u8]) -> [u8; 16] {
+    let mut p = [0; 16];
+    p[..bytes.len()].copy_from_slice(bytes);
+    p
+}
+
+#[test]
+fn test_extract_sgb_packets() {
+    let mut bank0 = vec![0u8; 0x4000];
+    // MLT_REQ for two players and MASK_EN freezing the screen
+    bank0[0x200..0x210].copy_from_slice(&packet(&[0x89, 0x01]));
+    bank0[0x213..0x223].copy_from_slice(&packet(&[0xB9, 0x01]));
+    // the start of the SDK initialisation sequence
+    bank0[0x300..0x310].copy_from_slice(&packet(&[0x79, 0x5D, 0x08, 0x00, 0x0B, 0x8C, 0xD0]));
+    bank0[0x310..0x320].copy_from_slice(&packet(&[0x79, 0x52, 0x08, 0x00, 0x0B, 0xA9, 0xE7]));
+
+    let packets = extract_sgb_packets(&bank0);
+    let found: Vec<(usize, SgbCommand)> = packets.iter().map(|p| (p.offset, p.command)).collect();
+    assert_eq!(
+        found,
+        vec![
+            (0x200, SgbCommand::MltReq),
+            (0x213, SgbCommand::MaskEn),
+            (0x300, SgbCommand::DataSnd),
+            (0x310, SgbCommand::DataSnd),
+        ]
+    );
+    assert_eq!(packets[0].data[1], 0x01);
+}
+
+#[test]
+fn test_no_false_positives() {
+    let mut bank0 = vec![0u8; 0x4000];
+    // a lone DATA_SND and a MLT_REQ with an invalid parameter
+    bank0[0x100..0x110].copy_from_slice(&packet(&[0x79, 0x5D, 0x08]));
+    bank0[0x200..0x210].copy_from_slice(&packet(&[0x89, 0x07]));
+    assert!(extract_sgb_packets(&bank0).is_empty());
+}
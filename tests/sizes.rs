@@ -0,0 +1,109 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{RAM_SIZES as RAM_SIZE_TABLE, ROM_SIZES as ROM_SIZE_TABLE};
+use mule_gb::{RAMSize, ROMSize, load_header};
+
+const ROM_SIZES: [ROMSize; 12] = [
+    ROMSize::NoBanking,
+    ROMSize::Banks4,
+    ROMSize::Banks8,
+    ROMSize::Banks16,
+    ROMSize::Banks32,
+    ROMSize::Banks64,
+    ROMSize::Banks128,
+    ROMSize::Banks256,
+    ROMSize::Banks512,
+    ROMSize::Banks72,
+    ROMSize::Banks80,
+    ROMSize::Banks96,
+];
+
+const RAM_SIZES: [RAMSize; 6] = [
+    RAMSize::None,
+    RAMSize::KB2,
+    RAMSize::KB8,
+    RAMSize::KB32,
+    RAMSize::KB128,
+    RAMSize::KB64,
+];
+
+fn header_rom(rom_size: u8, ram_size: u8) -> Vec<u8> {
+    let mut rom = make_rom(&RomSpec::default());
+    rom[0x148] = rom_size;
+    rom[0x149] = ram_size;
+    rom
+}
+
+#[test]
+fn test_size_codes() {
+    assert_eq!(ROMSize::Banks72.code(), 0x52);
+    assert_eq!(ROMSize::Banks512.code(), 0x08);
+    assert_eq!(RAMSize::KB64.code(), 0x05);
+    assert_eq!(RAMSize::KB128.code(), 0x04);
+}
+
+#[test]
+fn test_size_to_code_round_trip() {
+    for size in ROM_SIZES {
+        let header = load_header(&header_rom(size.code(), 0)).expect("header");
+        assert_eq!(header.rom_size.code(), size.code());
+        assert_eq!(header.rom_size.num_banks(), size.num_banks());
+    }
+    for size in RAM_SIZES {
+        let header = load_header(&header_rom(0, size.code())).expect("header");
+        assert_eq!(header.ram_size.code(), size.code());
+        assert_eq!(header.ram_size.bytes(), size.bytes());
+    }
+}
+
+#[test]
+fn test_code_to_size_round_trip() {
+    let mut known_rom = 0;
+    let mut known_ram = 0;
+    for code in 0..=255u8 {
+        let header = load_header(&header_rom(code, code)).expect("header");
+        assert_eq!(header.rom_size.code(), code);
+        assert_eq!(header.ram_size.code(), code);
+        if !matches!(header.rom_size, ROMSize::Unknown(_)) {
+            known_rom += 1;
+        }
+        if !matches!(header.ram_size, RAMSize::Unknown(_)) {
+            known_ram += 1;
+        }
+    }
+    assert_eq!(known_rom, ROM_SIZES.len());
+    assert_eq!(known_ram, RAM_SIZES.len());
+}
+
+#[test]
+fn test_size_tables() {
+    assert_eq!(ROM_SIZE_TABLE.len(), ROM_SIZES.len());
+    for (code, size) in ROM_SIZE_TABLE {
+        assert_eq!(size.code(), *code);
+        assert_eq!(
+            load_header(&header_rom(*code, 0x00)).unwrap().rom_size,
+            *size
+        );
+    }
+    assert_eq!(RAM_SIZE_TABLE.len(), RAM_SIZES.len());
+    for (code, size) in RAM_SIZE_TABLE {
+        assert_eq!(size.code(), *code);
+        assert_eq!(
+            load_header(&header_rom(0x00, *code)).unwrap().ram_size,
+            *size
+        );
+    }
+}
+
+// The spec encodes 128 KiB before 64 KiB, don't "fix" the order
+#[test]
+fn test_ram_size_out_of_order_codes() {
+    let header = load_header(&header_rom(0x00, 0x04)).expect("header");
+    assert_eq!(header.ram_size, RAMSize::KB128);
+    assert_eq!(header.ram_size.bytes(), 128 * 1024);
+
+    let header = load_header(&header_rom(0x00, 0x05)).expect("header");
+    assert_eq!(header.ram_size, RAMSize::KB64);
+    assert_eq!(header.ram_size.bytes(), 64 * 1024);
+}
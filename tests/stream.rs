@@ -0,0 +1,85 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{CartridgeType, GBError, ParseEvent, parse_streaming};
+
+#[test]
+fn test_parse_streaming() {
+    let rom = make_rom(&RomSpec {
+        cartridge_type: 0x01,
+        rom_size: 0x01,
+        ..Default::default()
+    });
+    let mut title = None;
+    let mut mbc1 = false;
+    let mut banks = Vec::new();
+    parse_streaming(&rom, &mut |event| match event {
+        ParseEvent::Title(t) => title = Some(t),
+        ParseEvent::CartridgeType(t) => mbc1 = matches!(t, CartridgeType::MBC1),
+        ParseEvent::BankStart(n) => banks.push(n),
+        _ => {}
+    })
+    .expect("parse");
+
+    assert_eq!(title.as_deref(), Some("TEST"));
+    assert!(mbc1);
+    assert_eq!(banks, vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn test_parse_streaming_error_before_events() {
+    let rom = make_rom(&RomSpec::default());
+    let mut events = 0;
+    let result = parse_streaming(&rom[..0x4000], &mut |_| events += 1);
+
+    assert!(matches!(result, Err(GBError::SizeMismatch { .. })));
+    assert_eq!(events, 0);
+}
+
+#[test]
+fn test_parse_streaming_event_order() {
+    let rom = make_rom(&RomSpec::default());
+    let mut events = Vec::new();
+    parse_streaming(&rom, &mut |event| {
+        events.push(match event {
+            ParseEvent::EntryPoint(_) => "entry_point",
+            ParseEvent::Logo { .. } => "logo",
+            ParseEvent::Title(_) => "title",
+            ParseEvent::ManufacturerCode(_) => "manufacturer_code",
+            ParseEvent::GbcFlag(_) => "gbc_flag",
+            ParseEvent::LicenseeCode(_) => "licensee_code",
+            ParseEvent::SgbFlag(_) => "sgb_flag",
+            ParseEvent::CartridgeType(_) => "cartridge_type",
+            ParseEvent::RomSize(_) => "rom_size",
+            ParseEvent::RamSize(_) => "ram_size",
+            ParseEvent::DestinationCode(_) => "destination_code",
+            ParseEvent::RomVersion(_) => "rom_version",
+            ParseEvent::HeaderChecksum { .. } => "header_checksum",
+            ParseEvent::GlobalChecksum { .. } => "global_checksum",
+            ParseEvent::BankStart(_) => "bank_start",
+        })
+    })
+    .expect("parse");
+
+    assert_eq!(
+        events,
+        vec![
+            "entry_point",
+            "logo",
+            "title",
+            "manufacturer_code",
+            "gbc_flag",
+            "licensee_code",
+            "sgb_flag",
+            "cartridge_type",
+            "rom_size",
+            "ram_size",
+            "destination_code",
+            "rom_version",
+            "header_checksum",
+            "global_checksum",
+            "bank_start",
+            "bank_start",
+        ]
+    );
+}
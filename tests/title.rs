@@ -0,0 +1,155 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{GBCFlag, LicenseeCode, load};
+
+#[test]
+fn test_cgb_title_and_manufacturer_code() {
+    let rom = make_rom(&RomSpec {
+        title: b"ZELDA",
+        manufacturer_code: b"AZ7E",
+        gbc_flag: 0x80,
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"01",
+        cartridge_type: 0x1B,
+        rom_size: 0x05,
+        ram_size: 0x03,
+        ..Default::default()
+    });
+    let header = load(&rom).expect("load").header;
+
+    assert_eq!(header.game_title, "ZELDA");
+    assert_eq!(header.manufacturer_code, "AZ7E");
+    assert!(matches!(header.gbc_flag, GBCFlag::GBCAndGB));
+    assert!(matches!(header.licensee_code, LicenseeCode::Nintendo));
+}
+
+#[test]
+fn test_dmg_title_uses_full_title_area() {
+    let rom = make_rom(&RomSpec {
+        title: b"SUPER MARIOLAND",
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"01",
+        ..Default::default()
+    });
+    let header = load(&rom).expect("load").header;
+
+    assert_eq!(header.game_title, "SUPER MARIOLAND");
+    assert_eq!(header.manufacturer_code, "");
+    assert!(matches!(header.gbc_flag, GBCFlag::GBOnly));
+}
+
+#[test]
+fn test_sixteen_character_dmg_title() {
+    let rom = make_rom(&RomSpec {
+        title: b"SIXTEEN CHARS OK",
+        ..Default::default()
+    });
+    let header = load(&rom).expect("load").header;
+
+    assert_eq!(header.game_title, "SIXTEEN CHARS OK");
+    assert!(matches!(header.gbc_flag, GBCFlag::GBOnly));
+    assert_eq!(header.gbc_flag_raw, b'K');
+}
+
+#[test]
+fn test_manufacturer_code_from_real_cgb_header() {
+    // header values of Pokemon Silver
+    let rom = make_rom(&RomSpec {
+        title: b"POKEMON_SLV",
+        manufacturer_code: b"AAXE",
+        gbc_flag: 0x80,
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"01",
+        cartridge_type: 0x10,
+        rom_size: 0x06,
+        ram_size: 0x03,
+        ..Default::default()
+    });
+    let header = load(&rom).expect("load").header;
+
+    assert_eq!(&rom[0x13F..0x143], b"AAXE");
+    assert_eq!(header.game_title, "POKEMON_SLV");
+    assert_eq!(header.manufacturer_code, "AAXE");
+}
+
+#[test]
+fn test_manufacturer_code_independent_of_licensee() {
+    let rom = make_rom(&RomSpec {
+        title: b"CGBGAME",
+        manufacturer_code: b"BCDE",
+        gbc_flag: 0xC0,
+        old_licensee_code: 0x01,
+        ..Default::default()
+    });
+    let header = load(&rom).expect("load").header;
+
+    assert_eq!(header.manufacturer_code, "BCDE");
+    assert!(matches!(header.licensee_code, LicenseeCode::Nintendo));
+}
+
+#[test]
+fn test_raw_title_bytes() {
+    let rom = make_rom(&RomSpec {
+        title: b"POKEMON_SLV",
+        manufacturer_code: b"AAXE",
+        gbc_flag: 0x80,
+        ..Default::default()
+    });
+    let header = load(&rom).expect("load").header;
+
+    assert_eq!(header.game_title, "POKEMON_SLV");
+    assert_eq!(&header.raw_title_bytes(), b"POKEMON_SLVAAXE\x80");
+    assert_eq!(header.raw_title_bytes(), rom[0x134..0x144]);
+}
+
+#[test]
+fn test_title_lossy_and_ascii() {
+    let rom = make_rom(&RomSpec {
+        title: b"GAME\x01\xFF\xFF",
+        ..Default::default()
+    });
+    let header = load(&rom).expect("load").header;
+
+    assert_eq!(header.title_lossy(), "GAME\u{1}\u{FFFD}\u{FFFD}");
+    assert_eq!(header.title_lossy(), header.game_title);
+    assert_eq!(header.title_ascii(), "GAME");
+}
+
+#[test]
+fn test_display_title() {
+    let rom = make_rom(&RomSpec {
+        title: b"Kirby dx  \0GARB",
+        ..Default::default()
+    });
+    let header = load(&rom).expect("load").header;
+
+    assert_eq!(header.game_title, "Kirby dx  ");
+    assert_eq!(header.title_lossy(), "Kirby dx  GARB");
+    assert_eq!(header.display_title(), "KIRBY DX");
+}
+
+#[test]
+fn test_full_title() {
+    let header = |title: &'static [u8], gbc_flag| {
+        load(&make_rom(&RomSpec {
+            title,
+            gbc_flag,
+            ..Default::default()
+        }))
+        .expect("load")
+        .header
+    };
+
+    let cut = header(b"SUPERMARIOLAND", 0x80);
+    assert_eq!(cut.game_title, "SUPERMARIOL");
+    assert_eq!(cut.manufacturer_code, "AND");
+    assert_eq!(cut.full_title(), None);
+
+    let cut = header(b"SUPERMARIOLANDX", 0x80);
+    assert_eq!(cut.game_title, "SUPERMARIOL");
+    assert_eq!(cut.full_title().as_deref(), Some("SUPERMARIOLANDX"));
+
+    assert_eq!(header(b"ZELDA", 0x80).full_title(), None);
+    assert_eq!(header(b"SUPERMARIOLANDX", 0x00).full_title(), None);
+}
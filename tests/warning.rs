@@ -0,0 +1,100 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{BANK_BYTES, GBError, Warning, load, load_header};
+
+fn warnings(cartridge_type: u8, ram_size: u8) -> Vec<Warning> {
+    let rom = make_rom(&RomSpec {
+        cartridge_type,
+        ram_size,
+        ..Default::default()
+    });
+    load_header(&rom).expect("header").validate_consistency()
+}
+
+#[test]
+fn test_consistent_headers() {
+    assert!(warnings(0x00, 0x00).is_empty());
+    assert!(warnings(0x03, 0x02).is_empty());
+    assert!(warnings(0x06, 0x00).is_empty());
+    assert!(warnings(0x0F, 0x00).is_empty());
+}
+
+#[test]
+fn test_inconsistent_headers() {
+    assert_eq!(warnings(0x00, 0x02), vec![Warning::RamDeclaredWithoutRam]);
+    assert_eq!(warnings(0x12, 0x00), vec![Warning::RamNotDeclared]);
+    assert_eq!(warnings(0x03, 0x00), vec![Warning::BatteryWithoutRam]);
+    assert_eq!(warnings(0x06, 0x02), vec![Warning::Mbc2ExternalRam]);
+}
+
+#[test]
+fn test_rom_version() {
+    let header = |rom_version| {
+        load_header(&make_rom(&RomSpec {
+            rom_version,
+            ..Default::default()
+        }))
+        .expect("header")
+    };
+
+    assert!(!header(0x00).is_revised());
+    assert!(header(0x01).is_revised());
+    assert!(header(0x02).validate_consistency().is_empty());
+    assert_eq!(
+        header(0xA5).validate_consistency(),
+        vec![Warning::ImplausibleRomVersion(0xA5)]
+    );
+}
+
+#[test]
+fn test_cgb_flag_mismatch() {
+    let cgb_warnings = |cartridge_type, old_licensee_code, destination_code| {
+        let rom = make_rom(&RomSpec {
+            gbc_flag: 0xC0,
+            cartridge_type,
+            old_licensee_code,
+            destination_code,
+            ..Default::default()
+        });
+        load_header(&rom).expect("header").validate_consistency()
+    };
+
+    // Hot-B only has an old licensee code
+    assert_eq!(
+        cgb_warnings(0x00, 0x09, 0x00),
+        vec![Warning::CgbFlagMismatch]
+    );
+    assert!(cgb_warnings(0x00, 0x09, 0x01).is_empty());
+    assert!(cgb_warnings(0x00, 0x01, 0x00).is_empty());
+    // MBC2 cartridges are checked as well
+    for mbc2 in [0x05, 0x06] {
+        assert_eq!(
+            cgb_warnings(mbc2, 0x09, 0x00),
+            vec![Warning::CgbFlagMismatch]
+        );
+        assert!(cgb_warnings(mbc2, 0x01, 0x00).is_empty());
+    }
+}
+
+#[test]
+fn test_unusual_rom_size() {
+    for (code, banks) in [(0x52, 72), (0x53, 80), (0x54, 96)] {
+        let mut rom = make_rom(&RomSpec {
+            rom_size: code,
+            ..Default::default()
+        });
+        assert_eq!(rom.len(), banks * BANK_BYTES);
+
+        let header = load_header(&rom).expect("header");
+        assert_eq!(
+            header.validate_consistency(),
+            vec![Warning::UnusualRomSize(code)]
+        );
+        assert_eq!(load(&rom).expect("load").bank_data.len(), banks);
+
+        // the length has to match the odd bank count exactly
+        rom.resize((banks - 8) * BANK_BYTES, 0x00);
+        assert!(matches!(load(&rom), Err(GBError::SizeMismatch { .. })));
+    }
+}
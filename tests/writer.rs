@@ -0,0 +1,142 @@
+mod common;
+
+use common::{RomSpec, make_rom};
+use mule_gb::{fix_checksums, load, verify_header_checksum};
+
+#[test]
+fn test_to_bytes_round_trip() {
+    let rom = make_rom(&RomSpec {
+        title: b"ROUNDTRIP",
+        cartridge_type: 0x13,
+        rom_size: 0x02,
+        ram_size: 0x03,
+        ..Default::default()
+    });
+    let gb = load(&rom).expect("load");
+    assert_eq!(gb.to_bytes(), rom);
+}
+
+#[test]
+fn test_to_bytes_round_trip_cgb() {
+    let rom = make_rom(&RomSpec {
+        title: b"CGBGAME",
+        manufacturer_code: b"ACGE",
+        gbc_flag: 0xC0,
+        old_licensee_code: 0x00,
+        cartridge_type: 0x1B,
+        rom_size: 0x01,
+        ram_size: 0x02,
+        ..Default::default()
+    });
+    let gb = load(&rom).expect("load");
+    assert_eq!(gb.to_bytes(), rom);
+}
+
+fn assert_round_trip(rom: &[u8]) {
+    let gb = load(rom).expect("load");
+    let bytes = gb.to_bytes();
+    assert_eq!(&bytes[0x100..0x150], &rom[0x100..0x150]);
+    assert_eq!(bytes, rom);
+}
+
+#[test]
+fn test_to_bytes_keeps_raw_title() {
+    // non ASCII title byte
+    let mut rom = make_rom(&RomSpec {
+        title: b"POKEMON",
+        ..Default::default()
+    });
+    rom[0x13B] = 0xFF;
+    assert_round_trip(&rom);
+
+    // bytes after an embedded NUL
+    assert_round_trip(&make_rom(&RomSpec {
+        title: b"KIRBY\0GARBAGE",
+        ..Default::default()
+    }));
+
+    // unknown CGB flag, parsed as GB only
+    let mut rom = make_rom(&RomSpec {
+        title: b"TITLE",
+        ..Default::default()
+    });
+    rom[0x143] = 0x84;
+    assert_round_trip(&rom);
+
+    // manufacturer code with an embedded NUL
+    assert_round_trip(&make_rom(&RomSpec {
+        title: b"CGBGAME",
+        manufacturer_code: b"AB\0D",
+        gbc_flag: 0x80,
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"01",
+        ..Default::default()
+    }));
+}
+
+#[test]
+fn test_to_bytes_reencodes_changed_title() {
+    let rom = make_rom(&RomSpec {
+        title: b"KIRBY\0GARBAGE",
+        ..Default::default()
+    });
+    let mut gb = load(&rom).expect("load");
+    gb.header.game_title = String::from("ZELDA");
+    let bytes = gb.to_bytes();
+    assert_eq!(&bytes[0x134..0x144], b"ZELDA\0\0\0\0\0\0\0\0\0\0\0");
+}
+
+#[test]
+fn test_to_bytes_keeps_raw_licensee_code() {
+    // old licensee code of Nintendo other than 0x01
+    assert_round_trip(&make_rom(&RomSpec {
+        old_licensee_code: 0x31,
+        ..Default::default()
+    }));
+
+    // new licensee code on a DMG cartridge
+    assert_round_trip(&make_rom(&RomSpec {
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"01",
+        ..Default::default()
+    }));
+
+    // new licensee code of Nintendo other than "01" on a SGB cartridge
+    assert_round_trip(&make_rom(&RomSpec {
+        old_licensee_code: 0x33,
+        new_licensee_code: *b"31",
+        sgb_flag: 0x03,
+        ..Default::default()
+    }));
+}
+
+#[test]
+fn test_to_bytes_round_trip_keeps_header_checksum() {
+    let mut rom = make_rom(&RomSpec {
+        title: b"CGBGAME",
+        manufacturer_code: b"AB\0D",
+        gbc_flag: 0x84,
+        old_licensee_code: 0x31,
+        sgb_flag: 0x03,
+        ..Default::default()
+    });
+    rom[0x13A] = 0xFF;
+    fix_checksums(&mut rom);
+    let gb = load(&rom).expect("load");
+    assert!(gb.header.checksum_valid);
+    let bytes = gb.to_bytes();
+    assert!(verify_header_checksum(&bytes));
+    assert_eq!(bytes, rom);
+}
+
+#[test]
+fn test_to_bytes_reencodes_changed_licensee_code() {
+    let rom = make_rom(&RomSpec {
+        old_licensee_code: 0x31,
+        ..Default::default()
+    });
+    let mut gb = load(&rom).expect("load");
+    gb.header.licensee_code = mule_gb::LicenseeCode::Capcom;
+    let bytes = gb.to_bytes();
+    assert_eq!(bytes[0x14B], 0x08);
+}